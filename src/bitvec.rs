@@ -1,9 +1,41 @@
-use crate::gvec::Gvec;
+mod arith;
+mod bitset;
+mod checked;
+mod cmp;
+mod concat;
+mod convert;
+mod format;
+mod kernel;
+mod matrix;
+mod mux;
+mod ones;
+mod parse;
+mod random;
+mod rank;
+mod reduce;
+mod roaring;
+mod serialize;
+mod shift;
+mod sim;
+mod slice;
+mod ternary;
+mod words;
+
+pub use bitset::BitSet;
+pub use checked::WidthMismatch;
+pub use convert::TryFromBitVecError;
+pub use matrix::BitMatrix;
+pub use ones::IterOnes;
+pub use parse::ParseBitVecError;
+pub use rank::RankSelect;
+pub use roaring::RoaringBitmap;
+pub use sim::SimVec;
+pub use slice::BitSlice;
+pub use ternary::TernaryBitVec;
+
 use core::panic;
 use rand::rngs::StdRng;
-use serde::{Deserialize, Serialize};
 use std::{
-    fmt::{self, Debug, Display},
     hash::Hash,
     ops::{
         BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign,
@@ -11,13 +43,28 @@ use std::{
         Not, RangeBounds,
     },
 };
+use words::Words;
 
-#[derive(Clone, Serialize, Deserialize)]
 pub struct BitVec {
-    bits: Gvec<u64>,
+    bits: Words,
     last_len: usize,
 }
 
+impl Clone for BitVec {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits.clone(),
+            last_len: self.last_len,
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.copy_from(source);
+    }
+}
+
 impl BitVec {
     pub const WORD_SIZE: usize = 64;
     pub const WORD_SIZE_MASK: usize = Self::WORD_SIZE - 1;
@@ -29,13 +76,7 @@ impl BitVec {
 
     #[inline]
     pub fn new_rand(num_word: usize, rng: &mut StdRng) -> Self {
-        if num_word == 0 {
-            return Self::default();
-        }
-        Self {
-            bits: Gvec::new_rand(num_word, rng),
-            last_len: 64,
-        }
+        Self::random(num_word * Self::WORD_SIZE, rng)
     }
 
     #[inline]
@@ -44,7 +85,7 @@ impl BitVec {
             return Self::default();
         }
         let v = if val { u64::MAX } else { 0 };
-        let mut bits = Gvec::from(vec![v; len / Self::WORD_SIZE]);
+        let mut bits = Words::from_elem(v, len / Self::WORD_SIZE);
         let mut last_len = len & Self::WORD_SIZE_MASK;
         if last_len == 0 {
             last_len = 64;
@@ -64,12 +105,11 @@ impl BitVec {
         res
     }
 
+    /// The unsigned value as a `usize`. Panics if it does not fit; see
+    /// [`try_to_usize`](Self::try_to_usize).
     pub fn to_usize(&self) -> usize {
-        assert!(self.len() as u32 <= usize::BITS);
-        if self.is_empty() {
-            return 0;
-        }
-        self.bits[0usize] as usize
+        self.try_to_usize()
+            .expect("bit-vector value does not fit in usize")
     }
 
     #[inline]
@@ -121,26 +161,51 @@ impl BitVec {
     /// Returns the bits in `range`, using the same bounds semantics as Rust ranges.
     #[inline]
     pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Self {
-        let l = match range.start_bound() {
-            Included(&l) => l,
-            Excluded(&l) => l.checked_add(1).expect("range start overflows usize"),
-            Unbounded => 0,
-        };
-        let h = match range.end_bound() {
-            Included(&h) => h.checked_add(1).expect("range end overflows usize"),
-            Excluded(&h) => h,
-            Unbounded => self.len(),
-        };
-
+        let (l, h) = resolve_range(range, self.len());
         debug_assert!(l <= h);
         debug_assert!(h <= self.len());
-        let mut res = BitVec::zero(h - l);
-        for idx in l..h {
-            res.set(idx - l, self.get(idx));
+        let len = h - l;
+        if len == 0 {
+            return Self::default();
         }
+        let wl = len.div_ceil(Self::WORD_SIZE);
+        let mut res = BitVec {
+            bits: (0..wl)
+                .map(|i| self.word_at(l + i * Self::WORD_SIZE))
+                .collect(),
+            last_len: len - (wl - 1) * Self::WORD_SIZE,
+        };
+        res.mask_last();
         res
     }
 
+    /// Returns the 64 bits starting at bit `offset`; bits past `len()` read as zero.
+    #[inline]
+    fn word_at(&self, offset: usize) -> u64 {
+        let (ws, bs) = (offset / Self::WORD_SIZE, offset % Self::WORD_SIZE);
+        let wl = self.word_len();
+        if ws >= wl {
+            return 0;
+        }
+        let mut w = self.bits[ws] >> bs;
+        if bs > 0 && ws + 1 < wl {
+            w |= self.bits[ws + 1] << (Self::WORD_SIZE - bs);
+        }
+        w
+    }
+
+    /// The words holding the `len()` bits.
+    #[inline]
+    fn words(&self) -> &[u64] {
+        &self.bits.as_slice()[..self.word_len()]
+    }
+
+    #[inline]
+    fn words_mut(&mut self) -> &mut [u64] {
+        let wl = self.word_len();
+        &mut self.bits.as_mut_slice()[..wl]
+    }
+
     #[inline]
     #[allow(unused)]
     fn last_word(&self) -> &u64 {
@@ -189,6 +254,115 @@ impl BitVec {
         }
     }
 
+    /// Sets the highest `n` bits to one.
+    #[inline]
+    fn fill_high(&mut self, n: usize) {
+        let start = self.len() - n.min(self.len());
+        let (ws, bs) = (start / Self::WORD_SIZE, start % Self::WORD_SIZE);
+        let wl = self.word_len();
+        if ws < wl {
+            self.bits[ws] |= u64::MAX << bs;
+            self.words_mut()[ws + 1..].fill(u64::MAX);
+            self.mask_last();
+        }
+    }
+
+    /// Changes the length to `len`, keeping the low bits and zeroing any new ones.
+    #[inline]
+    fn set_len(&mut self, len: usize) {
+        if len == 0 {
+            self.clear();
+            return;
+        }
+        let wl = len.div_ceil(Self::WORD_SIZE);
+        self.bits.resize(wl, 0);
+        self.last_len = len - (wl - 1) * Self::WORD_SIZE;
+        self.mask_last();
+    }
+
+    /// Resizes in place so that `len()` equals `new_len`, filling new bits with `val`.
+    #[inline]
+    pub fn resize(&mut self, new_len: usize, val: bool) {
+        let len = self.len();
+        self.set_len(new_len);
+        if val && new_len > len {
+            self.fill_high(new_len - len);
+        }
+    }
+
+    /// Appends the bits of `other` above the current most significant bit.
+    #[inline]
+    pub fn extend_from_bitvec(&mut self, other: &BitVec) {
+        if other.is_empty() {
+            return;
+        }
+        let len = self.len();
+        self.set_len(len + other.len());
+        let (ws, bs) = (len / Self::WORD_SIZE, len % Self::WORD_SIZE);
+        let wl = self.word_len();
+        for (i, &w) in other.words().iter().enumerate() {
+            self.bits[ws + i] |= w << bs;
+            if bs > 0 && ws + i + 1 < wl {
+                self.bits[ws + i + 1] |= w >> (Self::WORD_SIZE - bs);
+            }
+        }
+    }
+
+    /// Flips every bit in place.
+    #[inline]
+    pub fn not_assign(&mut self) {
+        for w in self.words_mut() {
+            *w = !*w;
+        }
+        self.mask_last();
+    }
+
+    /// Clears every bit that is set in `other`, i.e. `self &= !other`, in place.
+    #[inline]
+    pub fn and_not_assign(&mut self, other: &BitVec) {
+        assert!(self.len() == other.len());
+        kernel::and_not_assign(self.words_mut(), other.words());
+    }
+
+    /// Sets every bit to `val`.
+    #[inline]
+    pub fn set_all(&mut self, val: bool) {
+        self.words_mut().fill(if val { u64::MAX } else { 0 });
+        self.mask_last();
+    }
+
+    /// Sets the bits in `range` to `val`.
+    #[inline]
+    pub fn fill_range<R: RangeBounds<usize>>(&mut self, range: R, val: bool) {
+        let (l, h) = resolve_range(range, self.len());
+        assert!(l <= h && h <= self.len());
+        if l == h {
+            return;
+        }
+        let (ws, we) = (l / Self::WORD_SIZE, (h - 1) / Self::WORD_SIZE);
+        for i in ws..=we {
+            let lo = if i == ws { l % Self::WORD_SIZE } else { 0 };
+            let hi = if i == we {
+                (h - 1) % Self::WORD_SIZE
+            } else {
+                63
+            };
+            let mask = (u64::MAX >> (63 - hi)) & (u64::MAX << lo);
+            if val {
+                self.bits[i] |= mask;
+            } else {
+                self.bits[i] &= !mask;
+            }
+        }
+    }
+
+    /// Makes `self` a copy of `other`, reusing the existing allocation.
+    #[inline]
+    pub fn copy_from(&mut self, other: &BitVec) {
+        self.set_len(other.len());
+        self.words_mut().copy_from_slice(other.words());
+    }
+
     #[inline]
     pub fn zero(len: usize) -> Self {
         Self::from_elem(len, false)
@@ -211,12 +385,7 @@ impl BitVec {
     #[inline]
     pub fn is_zero(&self) -> bool {
         debug_assert!(!self.is_empty());
-        for i in 0..self.word_len() {
-            if self.bits[i] != 0 {
-                return false;
-            }
-        }
-        true
+        kernel::is_zero(self.words())
     }
 
     #[inline]
@@ -237,10 +406,8 @@ impl BitVec {
     pub fn is_ones(&self) -> bool {
         debug_assert!(!self.is_empty());
         let wl = self.word_len();
-        for i in 0..wl - 1 {
-            if self.bits[i] != u64::MAX {
-                return false;
-            }
+        if !kernel::is_ones(&self.words()[..wl - 1]) {
+            return false;
         }
         let mask = if self.last_len == 64 {
             u64::MAX
@@ -278,6 +445,22 @@ impl BitVec {
     }
 }
 
+/// Converts `range` into half-open `[l, h)` bounds, with `len` as the unbounded end.
+#[inline]
+fn resolve_range<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Included(&l) => l,
+        Excluded(&l) => l.checked_add(1).expect("range start overflows usize"),
+        Unbounded => 0,
+    };
+    let h = match range.end_bound() {
+        Included(&h) => h.checked_add(1).expect("range end overflows usize"),
+        Excluded(&h) => h,
+        Unbounded => len,
+    };
+    (l, h)
+}
+
 impl AsRef<Self> for BitVec {
     #[inline]
     fn as_ref(&self) -> &Self {
@@ -401,7 +584,7 @@ impl Default for BitVec {
     #[inline]
     fn default() -> Self {
         Self {
-            bits: Gvec::from([0]),
+            bits: Words::from([0]),
             last_len: 0,
         }
     }
@@ -410,15 +593,7 @@ impl Default for BitVec {
 impl PartialEq for BitVec {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        if self.len() != other.len() {
-            return false;
-        }
-        for i in 0..self.word_len() {
-            if self.bits[i] != other.bits[i] {
-                return false;
-            }
-        }
-        true
+        self.len() == other.len() && kernel::eq(self.words(), other.words())
     }
 }
 
@@ -427,24 +602,27 @@ impl Eq for BitVec {}
 impl Hash for BitVec {
     #[inline]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        for &bit in self.bits.iter().take(self.word_len()) {
-            bit.hash(state);
-        }
+        u64::hash_slice(self.words(), state);
         (self.last_len & Self::WORD_SIZE_MASK).hash(state);
     }
 }
 
+impl Not for BitVec {
+    type Output = BitVec;
+
+    #[inline]
+    fn not(mut self) -> BitVec {
+        self.not_assign();
+        self
+    }
+}
+
 impl Not for &BitVec {
     type Output = BitVec;
 
     #[inline]
     fn not(self) -> BitVec {
-        let mut res = self.clone();
-        for r in res.bits.iter_mut() {
-            *r = !*r;
-        }
-        res.mask_last();
-        res
+        !self.clone()
     }
 }
 
@@ -452,25 +630,17 @@ macro_rules! impl_bitop_owned_and_ref {
     (
         trait = $trait:ident,
         method = $method:ident,
-        op = $op:tt,
+        assign_trait = $assign_trait:ident,
+        assign_method = $assign_method:ident,
+        kernel = $kernel:ident,
     ) => {
         impl<R: AsRef<BitVec>> $trait<R> for BitVec {
             type Output = BitVec;
 
             #[inline]
-            fn $method(self, rhs: R) -> Self::Output {
-                assert!(self.len() == rhs.as_ref().len());
-                let mut res = BitVec {
-                    bits: self
-                        .bits
-                        .iter()
-                        .zip(rhs.as_ref().bits.iter())
-                        .map(|(s, r)| s $op r)
-                        .collect(),
-                    last_len: self.last_len,
-                };
-                res.mask_last();
-                res
+            fn $method(mut self, rhs: R) -> Self::Output {
+                self.$assign_method(rhs.as_ref());
+                self
             }
         }
 
@@ -479,37 +649,15 @@ macro_rules! impl_bitop_owned_and_ref {
 
             #[inline]
             fn $method(self, rhs: R) -> Self::Output {
-                assert!(self.len() == rhs.as_ref().len());
-                let mut res = BitVec {
-                    bits: self
-                        .bits
-                        .iter()
-                        .zip(rhs.as_ref().bits.iter())
-                        .map(|(s, r)| s $op r)
-                        .collect(),
-                    last_len: self.last_len,
-                };
-                res.mask_last();
-                res
+                self.clone().$method(rhs)
             }
         }
-    };
-}
 
-macro_rules! impl_bitassign_ref {
-    (
-        trait = $trait:ident,
-        method = $method:ident,
-        op = $op:tt,
-    ) => {
-        impl $trait<&BitVec> for BitVec {
+        impl $assign_trait<&BitVec> for BitVec {
             #[inline]
-            fn $method(&mut self, rhs: &BitVec) {
+            fn $assign_method(&mut self, rhs: &BitVec) {
                 assert!(self.len() == rhs.len());
-                for (s, r) in self.bits.iter_mut().zip(rhs.bits.iter()) {
-                    *s $op r;
-                }
-                self.mask_last();
+                kernel::$kernel(self.words_mut(), rhs.words());
             }
         }
     };
@@ -518,37 +666,25 @@ macro_rules! impl_bitassign_ref {
 impl_bitop_owned_and_ref!(
     trait = BitAnd,
     method = bitand,
-    op = &,
+    assign_trait = BitAndAssign,
+    assign_method = bitand_assign,
+    kernel = and_assign,
 );
 
 impl_bitop_owned_and_ref!(
     trait = BitOr,
     method = bitor,
-    op = |,
+    assign_trait = BitOrAssign,
+    assign_method = bitor_assign,
+    kernel = or_assign,
 );
 
 impl_bitop_owned_and_ref!(
     trait = BitXor,
     method = bitxor,
-    op = ^,
-);
-
-impl_bitassign_ref!(
-    trait = BitAndAssign,
-    method = bitand_assign,
-    op = &=,
-);
-
-impl_bitassign_ref!(
-    trait = BitOrAssign,
-    method = bitor_assign,
-    op = |=,
-);
-
-impl_bitassign_ref!(
-    trait = BitXorAssign,
-    method = bitxor_assign,
-    op = ^=,
+    assign_trait = BitXorAssign,
+    assign_method = bitxor_assign,
+    kernel = xor_assign,
 );
 
 impl<const N: usize> From<[bool; N]> for BitVec {
@@ -648,71 +784,6 @@ impl<'a> Extend<&'a bool> for BitVec {
     }
 }
 
-impl Debug for BitVec {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.is_empty() {
-            return write!(f, "[]");
-        }
-        let mut s = String::new();
-        for i in (0..self.len()).rev() {
-            if self.get(i) {
-                s.push('1');
-            } else {
-                s.push('0');
-            }
-        }
-        write!(f, "{s}")
-    }
-}
-
-impl Display for BitVec {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        Debug::fmt(&self, f)
-    }
-}
-
-impl fmt::LowerHex for BitVec {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.is_empty() {
-            return write!(f, "[]");
-        }
-        write!(f, "0x")?;
-        if self.is_zero() {
-            return write!(f, "0");
-        }
-        let wl = self.word_len();
-        write!(f, "{:x}", self.bits[wl - 1])?;
-        for i in (0..wl - 1).rev() {
-            write!(f, "{:016x}", self.bits[i])?;
-        }
-        Ok(())
-    }
-}
-
-impl fmt::UpperHex for BitVec {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.is_empty() {
-            return write!(f, "[]");
-        }
-        write!(f, "0x")?;
-        if self.is_zero() {
-            return write!(f, "0");
-        }
-        let wl = self.word_len();
-        write!(f, "{:X}", self.bits[wl - 1])?;
-        for i in (0..wl - 1).rev() {
-            write!(f, "{:016X}", self.bits[i])?;
-        }
-        Ok(())
-    }
-}
-
-impl fmt::Binary for BitVec {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        Debug::fmt(&self, f)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -722,8 +793,8 @@ mod tests {
     fn test0() {
         let v = [true, false, true, false, true];
         let bv = BitVec::from(v);
-        for i in 0..bv.len() {
-            assert_eq!(bv.get(i), v[i]);
+        for (i, &x) in v.iter().enumerate() {
+            assert_eq!(bv.get(i), x);
         }
     }
 
@@ -781,15 +852,16 @@ mod tests {
         assert_eq!(s.len(), 64);
         assert!(s.ends_with("11000000111001"));
 
-        assert_eq!(format!("{:x}", bv), "0x3039");
-        assert_eq!(format!("{:X}", bv), "0x3039");
+        assert_eq!(format!("{:#x}", bv), "0x3039");
+        assert_eq!(format!("{:#X}", bv), "0x3039");
+        assert_eq!(format!("{:x}", bv), "3039");
 
         let mut bv_large = BitVec::zero(128);
         bv_large.bits[0] = u64::MAX;
         bv_large.bits[1] = 1_u64;
 
-        assert_eq!(format!("{:x}", bv_large), "0x1ffffffffffffffff");
-        assert_eq!(format!("{:X}", bv_large), "0x1FFFFFFFFFFFFFFFF");
+        assert_eq!(format!("{:#x}", bv_large), "0x1ffffffffffffffff");
+        assert_eq!(format!("{:#X}", bv_large), "0x1FFFFFFFFFFFFFFFF");
 
         let s_large = format!("{}", bv_large);
         assert_eq!(s_large.len(), 128);
@@ -803,14 +875,14 @@ mod tests {
         let mut bv = BitVec::new();
         bv.extend([true, false, true]);
         assert_eq!(bv.len(), 3);
-        assert_eq!(bv.get(0), true);
-        assert_eq!(bv.get(1), false);
-        assert_eq!(bv.get(2), true);
+        assert!(bv.get(0));
+        assert!(!bv.get(1));
+        assert!(bv.get(2));
 
         bv.extend(&[false, true]);
         assert_eq!(bv.len(), 5);
-        assert_eq!(bv.get(3), false);
-        assert_eq!(bv.get(4), true);
+        assert!(!bv.get(3));
+        assert!(bv.get(4));
     }
 
     #[test]
@@ -847,7 +919,7 @@ mod tests {
     fn test_hash_eq() {
         let a = BitVec::ones(64);
         let b = BitVec {
-            bits: Gvec::from([u64::MAX, 0]),
+            bits: Words::from([u64::MAX, 0]),
             last_len: 0,
         };
         assert!(a == b);
@@ -893,4 +965,44 @@ mod tests {
             assert_eq!(slice.get(i), bv.get(3 + i), "inclusive range, i={i}");
         }
     }
+
+    #[test]
+    fn test_in_place_ops() {
+        let a = BitVec::from_iter((0..130).map(|i| i % 3 == 0));
+        let b = BitVec::from_iter((0..130).map(|i| i % 5 == 0));
+        let mut x = a.clone();
+        x.not_assign();
+        assert_eq!(x, !&a);
+        assert_eq!(x.count_ones(), 130 - a.count_ones());
+        let mut x = a.clone();
+        x.and_not_assign(&b);
+        assert_eq!(x, &a & !&b);
+        x.set_all(true);
+        assert!(x.is_ones());
+        x.set_all(false);
+        assert!(x.is_zero());
+        for (l, h) in [(0, 0), (0, 1), (3, 64), (63, 65), (10, 130), (64, 128)] {
+            let mut x = a.clone();
+            x.fill_range(l..h, true);
+            let mut y = b.clone();
+            y.fill_range(l..h, false);
+            for i in 0..130 {
+                assert_eq!(x.get(i), (l..h).contains(&i) || a.get(i));
+                assert_eq!(y.get(i), !(l..h).contains(&i) && b.get(i));
+            }
+        }
+        let mut x = BitVec::ones(3);
+        x.copy_from(&a);
+        assert_eq!(x, a);
+        x.copy_from(&BitVec::from("01"));
+        assert_eq!(x, BitVec::from("01"));
+        x.clone_from(&b);
+        assert_eq!(x, b);
+        let unnormalized = BitVec {
+            bits: Words::from([u64::MAX, 0]),
+            last_len: 0,
+        };
+        assert_eq!(BitVec::ones(64) & &unnormalized, BitVec::ones(64));
+        assert_eq!((unnormalized ^ BitVec::ones(64)).len(), 64);
+    }
 }
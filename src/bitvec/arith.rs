@@ -0,0 +1,370 @@
+use super::BitVec;
+use super::words::Words;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+impl BitVec {
+    #[inline]
+    fn add_in_place(&mut self, rhs: &BitVec) {
+        assert!(self.len() == rhs.len());
+        let mut carry = false;
+        for i in 0..self.word_len() {
+            let (s, c0) = self.bits[i].overflowing_add(rhs.bits[i]);
+            let (s, c1) = s.overflowing_add(carry as u64);
+            self.bits[i] = s;
+            carry = c0 | c1;
+        }
+        self.mask_last();
+    }
+
+    #[inline]
+    fn sub_in_place(&mut self, rhs: &BitVec) {
+        assert!(self.len() == rhs.len());
+        let mut borrow = false;
+        for i in 0..self.word_len() {
+            let (s, b0) = self.bits[i].overflowing_sub(rhs.bits[i]);
+            let (s, b1) = s.overflowing_sub(borrow as u64);
+            self.bits[i] = s;
+            borrow = b0 | b1;
+        }
+        self.mask_last();
+    }
+
+    #[inline]
+    fn mul_in_place(&mut self, rhs: &BitVec) {
+        assert!(self.len() == rhs.len());
+        let wl = self.word_len();
+        let mut res = Words::from_elem(0, wl);
+        for i in 0..wl {
+            let a = self.bits[i] as u128;
+            if a == 0 {
+                continue;
+            }
+            let mut carry = 0u128;
+            for j in 0..wl - i {
+                let t = a * rhs.bits[j] as u128 + res[i + j] as u128 + carry;
+                res[i + j] = t as u64;
+                carry = t >> 64;
+            }
+        }
+        for i in 0..wl {
+            self.bits[i] = res[i];
+        }
+        self.mask_last();
+    }
+
+    /// Two's complement negation in place, i.e. `self = 0 - self`.
+    #[inline]
+    pub fn neg_assign(&mut self) {
+        let mut borrow = false;
+        for i in 0..self.word_len() {
+            let (s, b0) = 0u64.overflowing_sub(self.bits[i]);
+            let (s, b1) = s.overflowing_sub(borrow as u64);
+            self.bits[i] = s;
+            borrow = b0 | b1;
+        }
+        self.mask_last();
+    }
+
+    /// Shifts left by one bit, shifting `bit` in at the bottom, and returns the bit shifted out.
+    #[inline]
+    fn shl1_in_place(&mut self, bit: bool) -> bool {
+        let wl = self.word_len();
+        let mut carry = bit as u64;
+        for i in 0..wl {
+            let w = self.bits[i];
+            self.bits[i] = (w << 1) | carry;
+            carry = w >> 63;
+        }
+        let out = if self.last_len == 64 || self.last_len == 0 {
+            carry
+        } else {
+            (self.bits[wl - 1] >> self.last_len) & 1
+        };
+        self.mask_last();
+        out != 0
+    }
+
+    /// Unsigned quotient and remainder. Division by zero yields all ones and the dividend.
+    fn udiv_rem(&self, rhs: &BitVec) -> (BitVec, BitVec) {
+        assert!(self.len() == rhs.len());
+        if self.is_empty() {
+            return (BitVec::new(), BitVec::new());
+        }
+        if rhs.is_zero() {
+            return (BitVec::ones(self.len()), self.clone());
+        }
+        if self.word_len() == 1 {
+            let (a, b) = (self.bits[0], rhs.bits[0]);
+            return (
+                BitVec::from_usize(self.len(), (a / b) as usize),
+                BitVec::from_usize(self.len(), (a % b) as usize),
+            );
+        }
+        let mut quot = BitVec::zero(self.len());
+        let mut rem = BitVec::zero(self.len());
+        for i in (0..self.len()).rev() {
+            let out = rem.shl1_in_place(self.get(i));
+            if out || rem.uge(rhs) {
+                rem.sub_in_place(rhs);
+                quot.set(i, true);
+            }
+        }
+        (quot, rem)
+    }
+
+    /// Unsigned division; division by zero yields all ones (SMT-LIB `bvudiv`).
+    #[inline]
+    pub fn udiv(&self, rhs: &BitVec) -> BitVec {
+        self.udiv_rem(rhs).0
+    }
+
+    /// Unsigned remainder; division by zero yields the dividend (SMT-LIB `bvurem`).
+    #[inline]
+    pub fn urem(&self, rhs: &BitVec) -> BitVec {
+        self.udiv_rem(rhs).1
+    }
+
+    #[inline]
+    fn abs(&self) -> BitVec {
+        if self.sign_bit() { -self } else { self.clone() }
+    }
+
+    /// Signed division truncating towards zero (SMT-LIB `bvsdiv`).
+    pub fn sdiv(&self, rhs: &BitVec) -> BitVec {
+        assert!(self.len() == rhs.len());
+        if self.is_empty() {
+            return BitVec::new();
+        }
+        let q = self.abs().udiv(&rhs.abs());
+        if self.sign_bit() != rhs.sign_bit() {
+            -q
+        } else {
+            q
+        }
+    }
+
+    /// Signed remainder whose sign follows the dividend (SMT-LIB `bvsrem`).
+    pub fn srem(&self, rhs: &BitVec) -> BitVec {
+        assert!(self.len() == rhs.len());
+        if self.is_empty() {
+            return BitVec::new();
+        }
+        let r = self.abs().urem(&rhs.abs());
+        if self.sign_bit() { -r } else { r }
+    }
+
+    /// Signed remainder whose sign follows the divisor (SMT-LIB `bvsmod`).
+    pub fn smod(&self, rhs: &BitVec) -> BitVec {
+        assert!(self.len() == rhs.len());
+        if self.is_empty() {
+            return BitVec::new();
+        }
+        let u = self.abs().urem(&rhs.abs());
+        if u.is_zero() {
+            return u;
+        }
+        match (self.sign_bit(), rhs.sign_bit()) {
+            (false, false) => u,
+            (true, false) => -u + rhs,
+            (false, true) => u + rhs,
+            (true, true) => -u,
+        }
+    }
+}
+
+macro_rules! impl_arith_owned_and_ref {
+    (
+        trait = $trait:ident,
+        method = $method:ident,
+        assign_trait = $assign_trait:ident,
+        assign_method = $assign_method:ident,
+        in_place = $in_place:ident,
+    ) => {
+        impl<R: AsRef<BitVec>> $trait<R> for BitVec {
+            type Output = BitVec;
+
+            #[inline]
+            fn $method(mut self, rhs: R) -> Self::Output {
+                self.$in_place(rhs.as_ref());
+                self
+            }
+        }
+
+        impl<R: AsRef<BitVec>> $trait<R> for &BitVec {
+            type Output = BitVec;
+
+            #[inline]
+            fn $method(self, rhs: R) -> Self::Output {
+                let mut res = self.clone();
+                res.$in_place(rhs.as_ref());
+                res
+            }
+        }
+
+        impl $assign_trait<&BitVec> for BitVec {
+            #[inline]
+            fn $assign_method(&mut self, rhs: &BitVec) {
+                self.$in_place(rhs);
+            }
+        }
+    };
+}
+
+impl_arith_owned_and_ref!(
+    trait = Add,
+    method = add,
+    assign_trait = AddAssign,
+    assign_method = add_assign,
+    in_place = add_in_place,
+);
+
+impl_arith_owned_and_ref!(
+    trait = Sub,
+    method = sub,
+    assign_trait = SubAssign,
+    assign_method = sub_assign,
+    in_place = sub_in_place,
+);
+
+impl_arith_owned_and_ref!(
+    trait = Mul,
+    method = mul,
+    assign_trait = MulAssign,
+    assign_method = mul_assign,
+    in_place = mul_in_place,
+);
+
+impl Neg for BitVec {
+    type Output = BitVec;
+
+    #[inline]
+    fn neg(mut self) -> BitVec {
+        self.neg_assign();
+        self
+    }
+}
+
+impl Neg for &BitVec {
+    type Output = BitVec;
+
+    #[inline]
+    fn neg(self) -> BitVec {
+        -self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    fn from_u128(len: usize, v: u128) -> BitVec {
+        let mut r = BitVec::zero(len);
+        for i in 0..len {
+            r.set(i, (v >> i) & 1 == 1);
+        }
+        r
+    }
+
+    fn to_u128(bv: &BitVec) -> u128 {
+        bv.iter()
+            .enumerate()
+            .fold(0, |acc, (i, b)| acc | ((b as u128) << i))
+    }
+
+    #[test]
+    fn test_arith_u128() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for w in [1, 7, 63, 64, 65, 100, 127, 128] {
+            let mask = if w == 128 { u128::MAX } else { (1 << w) - 1 };
+            for _ in 0..100 {
+                let a: u128 = rng.random::<u128>() & mask;
+                let b: u128 = rng.random::<u128>() & mask;
+                let (x, y) = (from_u128(w, a), from_u128(w, b));
+                assert_eq!(to_u128(&(&x + &y)), a.wrapping_add(b) & mask);
+                assert_eq!(to_u128(&(&x - &y)), a.wrapping_sub(b) & mask);
+                assert_eq!(to_u128(&(&x * &y)), a.wrapping_mul(b) & mask);
+                assert_eq!(to_u128(&-&x), a.wrapping_neg() & mask);
+            }
+        }
+    }
+
+    #[test]
+    fn test_arith_wide() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for w in [130, 191, 192, 300] {
+            let x = BitVec::from_iter((0..w).map(|_| rng.random::<bool>()));
+            let y = BitVec::from_iter((0..w).map(|_| rng.random::<bool>()));
+            assert!((&x + &y) - &y == x);
+            assert!((-&x + &x).is_zero());
+            assert!(&x * BitVec::one(w) == x);
+            assert!((&x * BitVec::zero(w)).is_zero());
+            assert!(BitVec::ones(w) + BitVec::one(w) == BitVec::zero(w));
+            assert!(&x * &y == &y * &x);
+            let mut z = x.clone();
+            z += &y;
+            z -= &x;
+            assert!(z == y);
+        }
+    }
+
+    fn to_i128(bv: &BitVec) -> i128 {
+        let v = to_u128(bv);
+        if bv.sign_bit() {
+            (v | (u128::MAX << bv.len())) as i128
+        } else {
+            v as i128
+        }
+    }
+
+    #[test]
+    fn test_div_u128() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for w in [1, 5, 64, 65, 100, 127] {
+            let mask: u128 = (1 << w) - 1;
+            for k in 0..200 {
+                let a: u128 = rng.random::<u128>() & mask;
+                let mut b: u128 = rng.random::<u128>() & mask;
+                if k % 3 == 0 {
+                    b >>= rng.random_range(0..w);
+                }
+                if k % 17 == 0 {
+                    b = 0;
+                }
+                let (x, y) = (from_u128(w, a), from_u128(w, b));
+                let (ia, ib) = (to_i128(&x), to_i128(&y));
+                if b == 0 {
+                    assert!(x.udiv(&y).is_ones());
+                    assert!(x.urem(&y) == x);
+                    assert!(x.srem(&y) == x);
+                    assert!(x.smod(&y) == x);
+                    let sdiv = if ia < 0 { 1 } else { mask };
+                    assert_eq!(to_u128(&x.sdiv(&y)), sdiv);
+                    continue;
+                }
+                assert_eq!(to_u128(&x.udiv(&y)), a / b);
+                assert_eq!(to_u128(&x.urem(&y)), a % b);
+                assert_eq!(to_u128(&x.sdiv(&y)), (ia.wrapping_div(ib) as u128) & mask);
+                assert_eq!(to_u128(&x.srem(&y)), (ia.wrapping_rem(ib) as u128) & mask);
+                let m = ia.rem_euclid(ib);
+                let smod = if m != 0 && ib < 0 { m + ib } else { m };
+                assert_eq!(to_u128(&x.smod(&y)), (smod as u128) & mask);
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_wide() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for w in [130, 200, 256] {
+            let x = BitVec::from_iter((0..w).map(|_| rng.random::<bool>()));
+            let y = BitVec::from_iter((0..w).map(|i| i < w / 2 && rng.random::<bool>()));
+            let (q, r) = (x.udiv(&y), x.urem(&y));
+            assert!(&q * &y + &r == x);
+            assert!(r.ult(&y));
+            let (q, r) = (x.sdiv(&y), x.srem(&y));
+            assert!(&q * &y + &r == x);
+            assert!(x.udiv(&BitVec::one(w)) == x);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use super::BitVec;
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+};
+
+/// A set of ids backed by a [`BitVec`] that grows as ids are inserted.
+pub struct BitSet<T = u32> {
+    bits: BitVec,
+    len: usize,
+    _t: PhantomData<T>,
+}
+
+impl<T> BitSet<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bits: BitVec::new(),
+            len: 0,
+            _t: PhantomData,
+        }
+    }
+
+    /// Creates an empty set that can hold ids below `n` without growing.
+    #[inline]
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            bits: BitVec::zero(n),
+            len: 0,
+            _t: PhantomData,
+        }
+    }
+
+    /// Number of ids in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Ids below this bound can be inserted without growing.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.bits.len()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bits.set_all(false);
+        self.len = 0;
+    }
+
+    /// The underlying bit-vector, with bit `i` set iff id `i` is in the set.
+    #[inline]
+    pub fn as_bitvec(&self) -> &BitVec {
+        &self.bits
+    }
+
+    #[inline]
+    fn grow(&mut self, n: usize) {
+        if n > self.bits.len() {
+            self.bits
+                .resize(n.next_multiple_of(BitVec::WORD_SIZE), false);
+        }
+    }
+
+    /// Applies `f` word by word to `self` and `other`, reading words past the end of
+    /// `other` as zero, and counts the resulting ids in the same pass.
+    #[inline]
+    fn zip_with(&mut self, other: &BitSet<T>, f: impl Fn(u64, u64) -> u64) {
+        let ow = other.bits.words();
+        let mut len = 0;
+        for (i, w) in self.bits.words_mut().iter_mut().enumerate() {
+            *w = f(*w, ow.get(i).copied().unwrap_or(0));
+            len += w.count_ones() as usize;
+        }
+        self.len = len;
+    }
+
+    #[inline]
+    pub fn union_with(&mut self, other: &BitSet<T>) {
+        self.grow(other.bits.len());
+        self.zip_with(other, |a, b| a | b);
+    }
+
+    #[inline]
+    pub fn intersect_with(&mut self, other: &BitSet<T>) {
+        self.zip_with(other, |a, b| a & b);
+    }
+
+    #[inline]
+    pub fn difference_with(&mut self, other: &BitSet<T>) {
+        self.zip_with(other, |a, b| a & !b);
+    }
+}
+
+impl<T: Into<u32> + Copy> BitSet<T> {
+    #[inline]
+    pub fn contains(&self, id: T) -> bool {
+        let i = id.into() as usize;
+        i < self.bits.len() && self.bits.get(i)
+    }
+
+    /// Adds `id`, growing the set if needed. Returns true if it was not present.
+    #[inline]
+    pub fn insert(&mut self, id: T) -> bool {
+        let i = id.into() as usize;
+        self.grow(i + 1);
+        if self.bits.get(i) {
+            return false;
+        }
+        self.bits.set(i, true);
+        self.len += 1;
+        true
+    }
+
+    /// Removes `id`. Returns true if it was present.
+    #[inline]
+    pub fn remove(&mut self, id: T) -> bool {
+        if !self.contains(id) {
+            return false;
+        }
+        self.bits.set(id.into() as usize, false);
+        self.len -= 1;
+        true
+    }
+}
+
+impl<T: From<u32>> BitSet<T> {
+    /// Iterates over the ids in increasing order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.bits.iter_ones().map(|i| T::from(i as u32))
+    }
+}
+
+impl<T> Default for BitSet<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for BitSet<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits.clone(),
+            len: self.len,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for BitSet<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.bits.iter_ones().eq(other.bits.iter_ones())
+    }
+}
+
+impl<T> Eq for BitSet<T> {}
+
+impl<T: Into<u32> + Copy> Extend<T> for BitSet<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for id in iter {
+            self.insert(id);
+        }
+    }
+}
+
+impl<T: Into<u32> + Copy> FromIterator<T> for BitSet<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut s = Self::new();
+        s.extend(iter);
+        s
+    }
+}
+
+impl<T> Debug for BitSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.bits.iter_ones()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_bitset() {
+        let mut s = BitSet::new();
+        assert!(s.is_empty());
+        assert!(!s.contains(1000u32));
+        assert!(s.insert(1000u32));
+        assert!(!s.insert(1000));
+        assert!(s.insert(3));
+        assert!(s.capacity() > 1000);
+        assert_eq!(s.len(), 2);
+        assert!(s.contains(3) && s.contains(1000) && !s.contains(4));
+        assert!(!s.remove(4));
+        assert!(s.remove(3));
+        assert!(!s.remove(5000));
+        assert_eq!(s.iter().collect::<Vec<_>>(), [1000]);
+        s.clear();
+        assert!(s.is_empty());
+        assert_eq!(format!("{:?}", BitSet::from_iter([5u32, 1])), "{1, 5}");
+    }
+
+    #[test]
+    fn test_bitset_ops() {
+        let a: Vec<u32> = (0..300).filter(|i| i % 3 == 0).collect();
+        let b: Vec<u32> = (0..100).filter(|i| i % 5 == 0).collect();
+        let (sa, sb) = (BitSet::from_iter(a.clone()), BitSet::from_iter(b.clone()));
+        let (ta, tb): (BTreeSet<u32>, BTreeSet<u32>) =
+            (a.into_iter().collect(), b.into_iter().collect());
+        let mut u = sb.clone();
+        u.union_with(&sa);
+        assert_eq!(
+            u.iter().collect::<Vec<_>>(),
+            ta.union(&tb).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(u.len(), ta.union(&tb).count());
+        let mut i = sa.clone();
+        i.intersect_with(&sb);
+        assert_eq!(
+            i.iter().collect::<Vec<_>>(),
+            ta.intersection(&tb).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(i.len(), ta.intersection(&tb).count());
+        let mut d = sa.clone();
+        d.difference_with(&sb);
+        assert_eq!(
+            d.iter().collect::<Vec<_>>(),
+            ta.difference(&tb).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(d.len(), ta.difference(&tb).count());
+        let mut e = BitSet::with_capacity(1000);
+        e.extend(i.iter());
+        assert_eq!(e, i);
+    }
+}
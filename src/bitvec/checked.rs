@@ -0,0 +1,112 @@
+use super::BitVec;
+use std::{borrow::Cow, error::Error, fmt};
+
+/// The error returned when the operands of a binary operation have different widths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WidthMismatch {
+    pub lhs: usize,
+    pub rhs: usize,
+}
+
+impl fmt::Display for WidthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bit-vector width mismatch: {} vs {}", self.lhs, self.rhs)
+    }
+}
+
+impl Error for WidthMismatch {}
+
+impl BitVec {
+    #[inline]
+    fn check_width(&self, rhs: &BitVec) -> Result<(), WidthMismatch> {
+        if self.len() == rhs.len() {
+            Ok(())
+        } else {
+            Err(WidthMismatch {
+                lhs: self.len(),
+                rhs: rhs.len(),
+            })
+        }
+    }
+
+    /// Zero-extends the shorter of `self` and `rhs` to the width of the longer.
+    #[inline]
+    fn zext_to_common<'a>(&'a self, rhs: &'a BitVec) -> (Cow<'a, BitVec>, Cow<'a, BitVec>) {
+        let (l, r) = (self.len(), rhs.len());
+        if l < r {
+            (Cow::Owned(self.zext(r - l)), Cow::Borrowed(rhs))
+        } else if r < l {
+            (Cow::Borrowed(self), Cow::Owned(rhs.zext(l - r)))
+        } else {
+            (Cow::Borrowed(self), Cow::Borrowed(rhs))
+        }
+    }
+}
+
+macro_rules! impl_width_policy {
+    ($($checked:ident, $zext:ident, $desc:literal, |$a:ident, $b:ident| $e:expr;)*) => {
+        impl BitVec {
+            $(
+                #[doc = concat!("Computes ", $desc, ", or returns an error if the widths differ.")]
+                #[inline]
+                pub fn $checked(&self, rhs: &BitVec) -> Result<BitVec, WidthMismatch> {
+                    self.check_width(rhs)?;
+                    let ($a, $b) = (self, rhs);
+                    Ok($e)
+                }
+
+                #[doc = concat!("Computes ", $desc, " after zero-extending the narrower operand.")]
+                #[inline]
+                pub fn $zext(&self, rhs: &BitVec) -> BitVec {
+                    let (l, r) = self.zext_to_common(rhs);
+                    let ($a, $b) = (l.as_ref(), r.as_ref());
+                    $e
+                }
+            )*
+        }
+    };
+}
+
+impl_width_policy!(
+    checked_and, and_zext, "`self & rhs`", |a, b| a & b;
+    checked_or, or_zext, "`self | rhs`", |a, b| a | b;
+    checked_xor, xor_zext, "`self ^ rhs`", |a, b| a ^ b;
+    checked_add, add_zext, "`self + rhs`", |a, b| a + b;
+    checked_sub, sub_zext, "`self - rhs`", |a, b| a - b;
+    checked_mul, mul_zext, "`self * rhs`", |a, b| a * b;
+    checked_udiv, udiv_zext, "the unsigned quotient", |a, b| a.udiv(b);
+    checked_urem, urem_zext, "the unsigned remainder", |a, b| a.urem(b);
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked() {
+        let a = BitVec::from("1100");
+        let b = BitVec::from("1010");
+        let c = BitVec::from("11");
+        assert_eq!(a.checked_and(&b), Ok(&a & &b));
+        assert_eq!(a.checked_add(&b), Ok(&a + &b));
+        assert_eq!(a.checked_urem(&b), Ok(a.urem(&b)));
+        let err = a.checked_xor(&c).unwrap_err();
+        assert_eq!(err, WidthMismatch { lhs: 4, rhs: 2 });
+        assert_eq!(err.to_string(), "bit-vector width mismatch: 4 vs 2");
+        assert!(c.checked_or(&a).is_err());
+    }
+
+    #[test]
+    fn test_zext() {
+        let a = BitVec::from("1100");
+        let c = BitVec::from("11");
+        assert_eq!(a.or_zext(&c), BitVec::from("1111"));
+        assert_eq!(c.and_zext(&a), BitVec::from("0000"));
+        assert_eq!(c.sub_zext(&a), BitVec::from("0111"));
+        assert_eq!(a.udiv_zext(&c), BitVec::from("0100"));
+        assert_eq!(a.xor_zext(&BitVec::new()), a);
+        let wide = BitVec::ones(70);
+        assert_eq!(wide.add_zext(&c).len(), 70);
+        assert_eq!(wide.add_zext(&c), BitVec::from_usize(70, 2));
+    }
+}
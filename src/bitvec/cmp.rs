@@ -0,0 +1,163 @@
+use super::BitVec;
+use std::cmp::Ordering;
+
+impl BitVec {
+    /// Unsigned comparison of two bit-vectors of the same width.
+    #[inline]
+    pub fn ucmp(&self, rhs: &BitVec) -> Ordering {
+        assert!(self.len() == rhs.len());
+        for (s, r) in self.words().iter().zip(rhs.words()).rev() {
+            match s.cmp(r) {
+                Ordering::Equal => (),
+                o => return o,
+            }
+        }
+        Ordering::Equal
+    }
+
+    /// Two's complement comparison of two bit-vectors of the same width.
+    #[inline]
+    pub fn scmp(&self, rhs: &BitVec) -> Ordering {
+        assert!(self.len() == rhs.len());
+        if self.is_empty() {
+            return Ordering::Equal;
+        }
+        match (self.sign_bit(), rhs.sign_bit()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => self.ucmp(rhs),
+        }
+    }
+
+    #[inline]
+    pub fn ult(&self, rhs: &BitVec) -> bool {
+        self.ucmp(rhs).is_lt()
+    }
+
+    #[inline]
+    pub fn ule(&self, rhs: &BitVec) -> bool {
+        self.ucmp(rhs).is_le()
+    }
+
+    #[inline]
+    pub fn ugt(&self, rhs: &BitVec) -> bool {
+        self.ucmp(rhs).is_gt()
+    }
+
+    #[inline]
+    pub fn uge(&self, rhs: &BitVec) -> bool {
+        self.ucmp(rhs).is_ge()
+    }
+
+    #[inline]
+    pub fn slt(&self, rhs: &BitVec) -> bool {
+        self.scmp(rhs).is_lt()
+    }
+
+    #[inline]
+    pub fn sle(&self, rhs: &BitVec) -> bool {
+        self.scmp(rhs).is_le()
+    }
+
+    #[inline]
+    pub fn sgt(&self, rhs: &BitVec) -> bool {
+        self.scmp(rhs).is_gt()
+    }
+
+    #[inline]
+    pub fn sge(&self, rhs: &BitVec) -> bool {
+        self.scmp(rhs).is_ge()
+    }
+}
+
+/// Orders by unsigned value first and by width second, so that bit-vectors of
+/// different widths can share an ordered collection.
+impl Ord for BitVec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (s, o) = (self.words(), other.words());
+        for i in (0..s.len().max(o.len())).rev() {
+            let sw = s.get(i).copied().unwrap_or(0);
+            let ow = o.get(i).copied().unwrap_or(0);
+            match sw.cmp(&ow) {
+                Ordering::Equal => (),
+                ord => return ord,
+            }
+        }
+        self.len().cmp(&other.len())
+    }
+}
+
+impl PartialOrd for BitVec {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_cmp() {
+        for w in [1, 3, 8] {
+            let m = (1i64 << w) - 1;
+            for a in 0..=m {
+                for b in 0..=m {
+                    let (x, y) = (
+                        BitVec::from_usize(w, a as usize),
+                        BitVec::from_usize(w, b as usize),
+                    );
+                    let sa = if a >> (w - 1) == 1 { a - m - 1 } else { a };
+                    let sb = if b >> (w - 1) == 1 { b - m - 1 } else { b };
+                    assert_eq!(x.ult(&y), a < b);
+                    assert_eq!(x.ule(&y), a <= b);
+                    assert_eq!(x.ugt(&y), a > b);
+                    assert_eq!(x.uge(&y), a >= b);
+                    assert_eq!(x.slt(&y), sa < sb);
+                    assert_eq!(x.sle(&y), sa <= sb);
+                    assert_eq!(x.sgt(&y), sa > sb);
+                    assert_eq!(x.sge(&y), sa >= sb);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmp_wide() {
+        let mut a = BitVec::zero(130);
+        let mut b = BitVec::zero(130);
+        a.set(129, true);
+        b.set(128, true);
+        b.set(0, true);
+        assert!(a.ugt(&b));
+        assert!(a.slt(&b));
+        a.set(129, false);
+        assert!(a.ult(&b));
+        assert!(a.slt(&b));
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut v = [
+            BitVec::from_usize(8, 3),
+            BitVec::from_usize(4, 3),
+            BitVec::from_usize(70, 1),
+            BitVec::new(),
+            BitVec::from_usize(2, 2),
+        ];
+        v.sort();
+        assert_eq!(v[0], BitVec::new());
+        assert_eq!(v[1], BitVec::from_usize(70, 1));
+        assert_eq!(v[2], BitVec::from_usize(2, 2));
+        assert_eq!(v[3], BitVec::from_usize(4, 3));
+        assert_eq!(v[4], BitVec::from_usize(8, 3));
+        let mut m = BTreeMap::new();
+        for (i, x) in v.iter().enumerate() {
+            m.insert(x.clone(), i);
+        }
+        assert_eq!(m.len(), 5);
+        assert_eq!(m[&BitVec::from_usize(4, 3)], 3);
+    }
+}
@@ -0,0 +1,107 @@
+use super::BitVec;
+
+impl BitVec {
+    /// Concatenates `self` as the high part with `lo` as the low part.
+    #[inline]
+    pub fn concat(&self, lo: &BitVec) -> BitVec {
+        let mut res = lo.clone();
+        res.extend_from_bitvec(self);
+        res
+    }
+
+    /// Zero-extends by `n` bits.
+    #[inline]
+    pub fn zext(&self, n: usize) -> BitVec {
+        let mut res = self.clone();
+        res.resize(self.len() + n, false);
+        res
+    }
+
+    /// Sign-extends by `n` bits. An empty bit-vector is extended with zeros.
+    #[inline]
+    pub fn sext(&self, n: usize) -> BitVec {
+        let mut res = self.clone();
+        let sign = !self.is_empty() && self.sign_bit();
+        res.resize(self.len() + n, sign);
+        res
+    }
+
+    /// Concatenates `k` copies of `self`.
+    #[inline]
+    pub fn repeat(&self, k: usize) -> BitVec {
+        let mut res = BitVec::new();
+        if self.is_empty() {
+            return res;
+        }
+        for _ in 0..k {
+            res.extend_from_bitvec(self);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::GHashSet;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    fn rand_bv(rng: &mut StdRng, w: usize) -> BitVec {
+        BitVec::from_iter((0..w).map(|_| rng.random::<bool>()))
+    }
+
+    #[test]
+    fn test_concat() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for hw in [0, 1, 5, 63, 64, 65, 130] {
+            for lw in [0, 1, 7, 63, 64, 65, 128] {
+                let (hi, lo) = (rand_bv(&mut rng, hw), rand_bv(&mut rng, lw));
+                let c = hi.concat(&lo);
+                let expect = BitVec::from_iter(lo.iter().chain(hi.iter()));
+                assert_eq!(c, expect, "hw={hw} lw={lw}");
+                let mut s = GHashSet::new();
+                s.insert(c);
+                s.insert(expect);
+                assert_eq!(s.len(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ext() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for w in [1, 5, 63, 64, 65, 127] {
+            let x = rand_bv(&mut rng, w);
+            for n in [0, 1, 63, 64, 65, 200] {
+                let z = x.zext(n);
+                let s = x.sext(n);
+                assert_eq!(z.len(), w + n);
+                assert_eq!(s.len(), w + n);
+                assert_eq!(z.slice(..w), x);
+                assert_eq!(s.slice(..w), x);
+                for i in w..w + n {
+                    assert!(!z.get(i));
+                    assert_eq!(s.get(i), x.sign_bit());
+                }
+                assert_eq!(z, BitVec::zero(n).concat(&x));
+            }
+        }
+        let neg = BitVec::from_usize(4, 0b1010);
+        assert_eq!(neg.sext(4), BitVec::from_usize(8, 0b11111010));
+        assert_eq!(neg.zext(4), BitVec::from_usize(8, 0b00001010));
+    }
+
+    #[test]
+    fn test_repeat() {
+        let x = BitVec::from("101");
+        assert_eq!(x.repeat(0), BitVec::new());
+        assert_eq!(x.repeat(1), x);
+        assert_eq!(x.repeat(3), BitVec::from("101101101"));
+        let y = BitVec::from_iter((0..70).map(|i| i % 3 == 0));
+        let r = y.repeat(5);
+        assert_eq!(r.len(), 350);
+        for i in 0..350 {
+            assert_eq!(r.get(i), y.get(i % 70));
+        }
+    }
+}
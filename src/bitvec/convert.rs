@@ -0,0 +1,182 @@
+use super::BitVec;
+use std::{error::Error, fmt};
+
+/// The error returned when a [`BitVec`] value does not fit in the target integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromBitVecError(());
+
+impl fmt::Display for TryFromBitVecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("bit-vector value out of range of the target integer type")
+    }
+}
+
+impl Error for TryFromBitVecError {}
+
+impl BitVec {
+    /// Creates a bit-vector of `width` bits from little-endian words, truncating or
+    /// zero-extending as needed.
+    #[inline]
+    pub fn from_words(words: &[u64], width: usize) -> Self {
+        let mut res = Self::zero(width);
+        let n = res.word_len().min(words.len());
+        res.words_mut()[..n].copy_from_slice(&words[..n]);
+        res.mask_last();
+        res
+    }
+
+    /// The little-endian words holding the bits; bits above `len()` are zero.
+    #[inline]
+    pub fn as_words(&self) -> &[u64] {
+        self.words()
+    }
+
+    /// Creates a bit-vector of `width` bits from little-endian bytes, truncating or
+    /// zero-extending as needed.
+    #[inline]
+    pub fn from_le_bytes(bytes: &[u8], width: usize) -> Self {
+        let mut res = Self::zero(width);
+        for (w, chunk) in res.words_mut().iter_mut().zip(bytes.chunks(8)) {
+            let mut buf = [0; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            *w = u64::from_le_bytes(buf);
+        }
+        res.mask_last();
+        res
+    }
+
+    /// The value as `ceil(len() / 8)` little-endian bytes.
+    #[inline]
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let n = self.len().div_ceil(8);
+        let mut bytes: Vec<u8> = self.words().iter().flat_map(|w| w.to_le_bytes()).collect();
+        bytes.truncate(n);
+        bytes
+    }
+
+    /// The value as `ceil(len() / 8)` big-endian bytes.
+    #[inline]
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_le_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// The unsigned value as a `usize`, or an error if it does not fit.
+    #[inline]
+    pub fn try_to_usize(&self) -> Result<usize, TryFromBitVecError> {
+        usize::try_from(self)
+    }
+
+    /// The low 128 bits, sign-extended from `len()` if `signed`.
+    #[inline]
+    fn low_u128(&self, signed: bool) -> u128 {
+        let mut v = self.word_at(0) as u128 | (self.word_at(Self::WORD_SIZE) as u128) << 64;
+        if signed && !self.is_empty() && self.len() < 128 && self.sign_bit() {
+            v |= u128::MAX << self.len();
+        }
+        v
+    }
+
+    /// Returns true if the value, read as unsigned, fits in `bits` bits.
+    #[inline]
+    fn fits_unsigned(&self, bits: usize) -> bool {
+        self.last_one().is_none_or(|i| i < bits)
+    }
+
+    /// Returns true if the value, read as signed, fits in `bits` bits.
+    #[inline]
+    fn fits_signed(&self, bits: usize) -> bool {
+        if self.len() <= bits {
+            return true;
+        }
+        let high = self.view(bits - 1..);
+        let ones = high.count_ones();
+        ones == 0 || ones == high.len()
+    }
+}
+
+macro_rules! impl_int_conversion {
+    ($fits:ident, $signed:expr, $($t:ty),*) => {
+        $(
+            impl From<$t> for BitVec {
+                #[inline]
+                fn from(v: $t) -> Self {
+                    let v = v as u128;
+                    Self::from_words(&[v as u64, (v >> 64) as u64], <$t>::BITS as usize)
+                }
+            }
+
+            impl TryFrom<&BitVec> for $t {
+                type Error = TryFromBitVecError;
+
+                #[inline]
+                fn try_from(bv: &BitVec) -> Result<Self, Self::Error> {
+                    if bv.$fits(<$t>::BITS as usize) {
+                        Ok(bv.low_u128($signed) as $t)
+                    } else {
+                        Err(TryFromBitVecError(()))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_int_conversion!(fits_unsigned, false, u8, u16, u32, u64, u128, usize);
+impl_int_conversion!(fits_signed, true, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int_conversion() {
+        assert_eq!(BitVec::from(0xa5u8), BitVec::from("10100101"));
+        assert_eq!(BitVec::from(-1i16), BitVec::ones(16));
+        assert_eq!(BitVec::from(u128::MAX).len(), 128);
+        assert!(BitVec::from(u128::MAX).is_ones());
+        assert_eq!(BitVec::from(i128::MIN).last_one(), Some(127));
+        for v in [0i64, 1, -1, 127, -128, 300, i64::MIN, i64::MAX] {
+            let bv = BitVec::from(v);
+            assert_eq!(i64::try_from(&bv), Ok(v));
+            assert_eq!(i128::try_from(&bv), Ok(v as i128));
+            assert_eq!(u64::try_from(&bv), Ok(v as u64));
+            assert_eq!(i8::try_from(&bv).ok(), i8::try_from(v).ok());
+            assert_eq!(u8::try_from(&bv).ok(), u8::try_from(v as u64).ok());
+        }
+        let x = BitVec::from("101");
+        assert_eq!(u8::try_from(&x), Ok(5));
+        assert_eq!(i8::try_from(&x), Ok(-3));
+        assert_eq!(i32::try_from(&BitVec::new()), Ok(0));
+        let wide = BitVec::from(-2i64).sext(100);
+        assert_eq!(i16::try_from(&wide), Ok(-2));
+        assert!(u64::try_from(&wide).is_err());
+        assert!(BitVec::ones(200).try_to_usize().is_err());
+        assert_eq!(BitVec::from_usize(200, 7).try_to_usize(), Ok(7));
+    }
+
+    #[test]
+    fn test_bytes_words() {
+        let bv = BitVec::from_words(&[0x0123456789abcdef, 0xff], 72);
+        assert_eq!(bv.len(), 72);
+        assert_eq!(bv.as_words(), [0x0123456789abcdef, 0xff]);
+        let le = bv.to_le_bytes();
+        assert_eq!(le, [0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01, 0xff]);
+        assert_eq!(
+            bv.to_be_bytes(),
+            le.iter().rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(BitVec::from_le_bytes(&le, 72), bv);
+        assert_eq!(BitVec::from_le_bytes(&le, 4), BitVec::from("1111"));
+        assert_eq!(
+            BitVec::from_le_bytes(&[0xff], 12),
+            BitVec::from_usize(12, 0xff)
+        );
+        assert_eq!(
+            BitVec::from_words(&[u64::MAX; 3], 65).as_words(),
+            [u64::MAX, 1]
+        );
+        assert!(BitVec::from_le_bytes(&[], 0).to_le_bytes().is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+use super::BitVec;
+use std::fmt::{self, Debug, Display};
+
+/// Largest power of ten that fits in a `u64`.
+const DEC_CHUNK: u64 = 10_000_000_000_000_000_000;
+const DEC_CHUNK_DIGITS: usize = 19;
+
+impl BitVec {
+    /// Digits of a power-of-two radix with `digit_bits` bits per digit, most significant first.
+    pub(super) fn radix_digits(&self, digit_bits: usize, upper: bool, strip: bool) -> String {
+        let mask = (1u64 << digit_bits) - 1;
+        let n = self.len().div_ceil(digit_bits);
+        let mut s = String::with_capacity(n);
+        for i in (0..n).rev() {
+            let d = (self.word_at(i * digit_bits) & mask) as u32;
+            if strip && d == 0 && s.is_empty() && i > 0 {
+                continue;
+            }
+            let c = char::from_digit(d, 1 << digit_bits).unwrap();
+            s.push(if upper { c.to_ascii_uppercase() } else { c });
+        }
+        s
+    }
+
+    /// Unsigned decimal representation, for any width.
+    pub fn to_dec_string(&self) -> String {
+        let mut words = self.words().to_vec();
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        let mut chunks = Vec::new();
+        while !words.is_empty() {
+            let mut rem = 0u128;
+            for w in words.iter_mut().rev() {
+                let cur = (rem << 64) | *w as u128;
+                *w = (cur / DEC_CHUNK as u128) as u64;
+                rem = cur % DEC_CHUNK as u128;
+            }
+            chunks.push(rem as u64);
+            while words.last() == Some(&0) {
+                words.pop();
+            }
+        }
+        let Some(top) = chunks.pop() else {
+            return "0".to_string();
+        };
+        let mut s = top.to_string();
+        for c in chunks.iter().rev() {
+            s.push_str(&format!("{c:0DEC_CHUNK_DIGITS$}"));
+        }
+        s
+    }
+
+    /// Two's complement decimal representation, for any width.
+    pub fn to_signed_dec_string(&self) -> String {
+        if !self.is_empty() && self.sign_bit() {
+            format!("-{}", (-self).to_dec_string())
+        } else {
+            self.to_dec_string()
+        }
+    }
+}
+
+impl Debug for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.pad("[]");
+        }
+        f.pad_integral(true, "", &self.radix_digits(1, false, false))
+    }
+}
+
+impl Display for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Binary::fmt(self, f)
+    }
+}
+
+impl fmt::Binary for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.pad("[]");
+        }
+        f.pad_integral(true, "0b", &self.radix_digits(1, false, false))
+    }
+}
+
+impl fmt::Octal for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.pad("[]");
+        }
+        f.pad_integral(true, "0o", &self.radix_digits(3, false, true))
+    }
+}
+
+impl fmt::LowerHex for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.pad("[]");
+        }
+        f.pad_integral(true, "0x", &self.radix_digits(4, false, true))
+    }
+}
+
+impl fmt::UpperHex for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.pad("[]");
+        }
+        f.pad_integral(true, "0x", &self.radix_digits(4, true, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dec() {
+        assert_eq!(BitVec::new().to_dec_string(), "0");
+        assert_eq!(BitVec::zero(100).to_dec_string(), "0");
+        assert_eq!(BitVec::from_usize(8, 200).to_dec_string(), "200");
+        assert_eq!(BitVec::from_usize(8, 200).to_signed_dec_string(), "-56");
+        assert_eq!(BitVec::from_usize(8, 0x80).to_signed_dec_string(), "-128");
+        assert_eq!(BitVec::from_usize(8, 127).to_signed_dec_string(), "127");
+        assert_eq!(BitVec::ones(64).to_dec_string(), u64::MAX.to_string());
+        assert_eq!(BitVec::ones(128).to_dec_string(), u128::MAX.to_string());
+        assert_eq!(
+            (BitVec::one(128) << 127).to_signed_dec_string(),
+            i128::MIN.to_string()
+        );
+        let ten19 = BitVec::from_usize(64, DEC_CHUNK as usize);
+        assert_eq!(ten19.to_dec_string(), "10000000000000000000");
+        let s = "123456789012345678901234567890123456789012345678901234567890";
+        let big = BitVec::from_str_radix(s, 10, 200).unwrap();
+        assert_eq!(big.to_dec_string(), s);
+        assert_eq!((-&big).to_signed_dec_string(), format!("-{s}"));
+    }
+
+    #[test]
+    fn test_fmt_flags() {
+        let bv = BitVec::from_usize(6, 0b101011);
+        assert_eq!(format!("{bv:o}"), "53");
+        assert_eq!(format!("{bv:#o}"), "0o53");
+        assert_eq!(format!("{bv:#b}"), "0b101011");
+        assert_eq!(format!("{bv:#}"), "0b101011");
+        assert_eq!(format!("{bv:>10}"), "    101011");
+        assert_eq!(format!("{bv:*<10}"), "101011****");
+        assert_eq!(format!("{bv:#010x}"), "0x0000002b");
+        assert_eq!(format!("{bv:8X}"), "      2B");
+        assert_eq!(format!("{:>4?}", BitVec::new()), "  []");
+        assert_eq!(format!("{:o}", BitVec::zero(7)), "0");
+        let wide = BitVec::from_iter((0..70).map(|i| i % 2 == 0));
+        assert_eq!(format!("{wide:o}"), "52525252525252525252525");
+        assert_eq!(format!("{wide:x}"), "155555555555555555");
+    }
+}
@@ -0,0 +1,379 @@
+//! Bulk word kernels used by [`BitVec`](super::BitVec).
+//!
+//! With the `simd` feature on x86_64 the kernels use AVX2 when the CPU supports it and
+//! SSE2 otherwise; everywhere else they use the scalar loops in [`scalar`].
+
+pub(super) mod scalar {
+    #[inline]
+    pub fn and_assign(dst: &mut [u64], src: &[u64]) {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d &= s;
+        }
+    }
+
+    #[inline]
+    pub fn or_assign(dst: &mut [u64], src: &[u64]) {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d |= s;
+        }
+    }
+
+    #[inline]
+    pub fn xor_assign(dst: &mut [u64], src: &[u64]) {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d ^= s;
+        }
+    }
+
+    #[inline]
+    pub fn and_not_assign(dst: &mut [u64], src: &[u64]) {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d &= !s;
+        }
+    }
+
+    #[inline]
+    pub fn eq(a: &[u64], b: &[u64]) -> bool {
+        a == b
+    }
+
+    #[inline]
+    pub fn is_zero(a: &[u64]) -> bool {
+        a.iter().all(|&w| w == 0)
+    }
+
+    #[inline]
+    pub fn is_ones(a: &[u64]) -> bool {
+        a.iter().all(|&w| w == u64::MAX)
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod x86 {
+    use super::scalar;
+    use std::arch::x86_64::*;
+
+    macro_rules! impl_binary {
+        ($name:ident, $feature:literal, $vec:ty, $lanes:expr, $load:ident, $store:ident, |$a:ident, $b:ident| $e:expr) => {
+            #[target_feature(enable = $feature)]
+            pub fn $name(dst: &mut [u64], src: &[u64]) {
+                let n = dst.len().min(src.len());
+                let chunks = n / $lanes;
+                for i in 0..chunks {
+                    // SAFETY: `i * $lanes + $lanes <= n` words are in bounds of both slices.
+                    unsafe {
+                        let d = dst.as_mut_ptr().add(i * $lanes) as *mut $vec;
+                        let s = src.as_ptr().add(i * $lanes) as *const $vec;
+                        let ($a, $b) = ($load(d), $load(s));
+                        $store(d, $e);
+                    }
+                }
+                let tail = chunks * $lanes;
+                scalar::$name(&mut dst[tail..n], &src[tail..n]);
+            }
+        };
+    }
+
+    pub mod sse2 {
+        use super::*;
+
+        impl_binary!(
+            and_assign,
+            "sse2",
+            __m128i,
+            2,
+            _mm_loadu_si128,
+            _mm_storeu_si128,
+            |a, b| _mm_and_si128(a, b)
+        );
+        impl_binary!(
+            or_assign,
+            "sse2",
+            __m128i,
+            2,
+            _mm_loadu_si128,
+            _mm_storeu_si128,
+            |a, b| _mm_or_si128(a, b)
+        );
+        impl_binary!(
+            xor_assign,
+            "sse2",
+            __m128i,
+            2,
+            _mm_loadu_si128,
+            _mm_storeu_si128,
+            |a, b| _mm_xor_si128(a, b)
+        );
+        impl_binary!(
+            and_not_assign,
+            "sse2",
+            __m128i,
+            2,
+            _mm_loadu_si128,
+            _mm_storeu_si128,
+            |a, b| _mm_andnot_si128(b, a)
+        );
+
+        /// Folds `a` (and `b`, if given) into one vector with `f`, starting from `init`,
+        /// and returns it with the scalar tail.
+        #[target_feature(enable = "sse2")]
+        fn fold(
+            a: &[u64],
+            b: Option<&[u64]>,
+            init: __m128i,
+            f: impl Fn(__m128i, __m128i, __m128i) -> __m128i,
+        ) -> (__m128i, usize) {
+            let chunks = a.len() / 2;
+            let mut acc = init;
+            for i in 0..chunks {
+                // SAFETY: `2 * i + 2 <= a.len()`, and `b` has the same length as `a`.
+                unsafe {
+                    let x = _mm_loadu_si128(a.as_ptr().add(i * 2) as *const __m128i);
+                    let y = match b {
+                        Some(b) => _mm_loadu_si128(b.as_ptr().add(i * 2) as *const __m128i),
+                        None => x,
+                    };
+                    acc = f(acc, x, y);
+                }
+            }
+            (acc, chunks * 2)
+        }
+
+        #[target_feature(enable = "sse2")]
+        fn all_zero(v: __m128i) -> bool {
+            _mm_movemask_epi8(_mm_cmpeq_epi8(v, _mm_setzero_si128())) == 0xffff
+        }
+
+        #[target_feature(enable = "sse2")]
+        pub fn eq(a: &[u64], b: &[u64]) -> bool {
+            if a.len() != b.len() {
+                return false;
+            }
+            let (acc, tail) = fold(a, Some(b), _mm_setzero_si128(), |acc, x, y| {
+                _mm_or_si128(acc, _mm_xor_si128(x, y))
+            });
+            all_zero(acc) && a[tail..] == b[tail..]
+        }
+
+        #[target_feature(enable = "sse2")]
+        pub fn is_zero(a: &[u64]) -> bool {
+            let (acc, tail) = fold(a, None, _mm_setzero_si128(), |acc, x, _| {
+                _mm_or_si128(acc, x)
+            });
+            all_zero(acc) && scalar::is_zero(&a[tail..])
+        }
+
+        #[target_feature(enable = "sse2")]
+        pub fn is_ones(a: &[u64]) -> bool {
+            let ones = _mm_set1_epi64x(-1);
+            let (acc, tail) = fold(a, None, ones, |acc, x, _| _mm_and_si128(acc, x));
+            all_zero(_mm_xor_si128(acc, ones)) && scalar::is_ones(&a[tail..])
+        }
+    }
+
+    pub mod avx2 {
+        use super::*;
+
+        impl_binary!(
+            and_assign,
+            "avx2",
+            __m256i,
+            4,
+            _mm256_loadu_si256,
+            _mm256_storeu_si256,
+            |a, b| _mm256_and_si256(a, b)
+        );
+        impl_binary!(
+            or_assign,
+            "avx2",
+            __m256i,
+            4,
+            _mm256_loadu_si256,
+            _mm256_storeu_si256,
+            |a, b| _mm256_or_si256(a, b)
+        );
+        impl_binary!(
+            xor_assign,
+            "avx2",
+            __m256i,
+            4,
+            _mm256_loadu_si256,
+            _mm256_storeu_si256,
+            |a, b| _mm256_xor_si256(a, b)
+        );
+        impl_binary!(
+            and_not_assign,
+            "avx2",
+            __m256i,
+            4,
+            _mm256_loadu_si256,
+            _mm256_storeu_si256,
+            |a, b| _mm256_andnot_si256(b, a)
+        );
+
+        /// Folds `a` (and `b`, if given) into one vector with `f`, starting from `init`,
+        /// and returns it with the scalar tail.
+        #[target_feature(enable = "avx2")]
+        fn fold(
+            a: &[u64],
+            b: Option<&[u64]>,
+            init: __m256i,
+            f: impl Fn(__m256i, __m256i, __m256i) -> __m256i,
+        ) -> (__m256i, usize) {
+            let chunks = a.len() / 4;
+            let mut acc = init;
+            for i in 0..chunks {
+                // SAFETY: `4 * i + 4 <= a.len()`, and `b` has the same length as `a`.
+                unsafe {
+                    let x = _mm256_loadu_si256(a.as_ptr().add(i * 4) as *const __m256i);
+                    let y = match b {
+                        Some(b) => _mm256_loadu_si256(b.as_ptr().add(i * 4) as *const __m256i),
+                        None => x,
+                    };
+                    acc = f(acc, x, y);
+                }
+            }
+            (acc, chunks * 4)
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub fn eq(a: &[u64], b: &[u64]) -> bool {
+            if a.len() != b.len() {
+                return false;
+            }
+            let (acc, tail) = fold(a, Some(b), _mm256_setzero_si256(), |acc, x, y| {
+                _mm256_or_si256(acc, _mm256_xor_si256(x, y))
+            });
+            _mm256_testz_si256(acc, acc) == 1 && a[tail..] == b[tail..]
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub fn is_zero(a: &[u64]) -> bool {
+            let (acc, tail) = fold(a, None, _mm256_setzero_si256(), |acc, x, _| {
+                _mm256_or_si256(acc, x)
+            });
+            _mm256_testz_si256(acc, acc) == 1 && scalar::is_zero(&a[tail..])
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub fn is_ones(a: &[u64]) -> bool {
+            let ones = _mm256_set1_epi64x(-1);
+            let (acc, tail) = fold(a, None, ones, |acc, x, _| _mm256_and_si256(acc, x));
+            _mm256_testc_si256(acc, ones) == 1 && scalar::is_ones(&a[tail..])
+        }
+    }
+}
+
+/// Number of words below which the scalar loop is used directly.
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+const SIMD_MIN_WORDS: usize = 4;
+
+macro_rules! dispatch {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        $(
+            #[inline]
+            pub(super) fn $name($($arg: $ty),*) $(-> $ret)? {
+                #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+                {
+                    let n = [$($arg.len()),*][0];
+                    if n >= SIMD_MIN_WORDS {
+                        // SAFETY: the AVX2 kernel only runs when the CPU supports AVX2,
+                        // and SSE2 is part of the x86_64 baseline.
+                        return unsafe {
+                            if is_x86_feature_detected!("avx2") {
+                                x86::avx2::$name($($arg),*)
+                            } else {
+                                x86::sse2::$name($($arg),*)
+                            }
+                        };
+                    }
+                }
+                scalar::$name($($arg),*)
+            }
+        )*
+    };
+}
+
+dispatch! {
+    fn and_assign(dst: &mut [u64], src: &[u64]);
+    fn or_assign(dst: &mut [u64], src: &[u64]);
+    fn xor_assign(dst: &mut [u64], src: &[u64]);
+    fn and_not_assign(dst: &mut [u64], src: &[u64]);
+    fn eq(a: &[u64], b: &[u64]) -> bool;
+    fn is_zero(a: &[u64]) -> bool;
+    fn is_ones(a: &[u64]) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    type Binary = fn(&mut [u64], &[u64]);
+
+    #[test]
+    fn test_kernels_match_scalar() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let binary: [(Binary, Binary); 4] = [
+            (and_assign, scalar::and_assign),
+            (or_assign, scalar::or_assign),
+            (xor_assign, scalar::xor_assign),
+            (and_not_assign, scalar::and_not_assign),
+        ];
+        for n in [0, 1, 2, 3, 4, 5, 7, 8, 9, 31, 64, 100] {
+            let a: Vec<u64> = (0..n).map(|_| rng.random()).collect();
+            let b: Vec<u64> = (0..n).map(|_| rng.random()).collect();
+            for (simd, scalar) in binary {
+                let (mut x, mut y) = (a.clone(), a.clone());
+                simd(&mut x, &b);
+                scalar(&mut y, &b);
+                assert_eq!(x, y, "n={n}");
+            }
+            assert_eq!(eq(&a, &b), scalar::eq(&a, &b));
+            assert!(eq(&a, &a));
+            let zero = vec![0; n];
+            let ones = vec![u64::MAX; n];
+            assert!(is_zero(&zero) && is_ones(&ones));
+            assert_eq!(is_zero(&a), scalar::is_zero(&a));
+            assert_eq!(is_ones(&a), scalar::is_ones(&a));
+            for i in 0..n {
+                let (mut z, mut o, mut c) = (zero.clone(), ones.clone(), a.clone());
+                z[i] = 1 << (i % 64);
+                o[i] = !(1 << (i % 64));
+                c[i] ^= 1 << (i % 64);
+                assert!(!is_zero(&z) && !is_ones(&o) && !eq(&a, &c), "n={n} i={i}");
+            }
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[test]
+    fn test_x86_kernels_match_scalar() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let avx2 = is_x86_feature_detected!("avx2");
+        for n in [0, 1, 2, 3, 4, 5, 9, 33] {
+            let a: Vec<u64> = (0..n).map(|_| rng.random()).collect();
+            let b: Vec<u64> = (0..n).map(|_| rng.random()).collect();
+            let mut expect = a.clone();
+            scalar::xor_assign(&mut expect, &b);
+            let ones = vec![u64::MAX; n];
+            // SAFETY: SSE2 is part of the x86_64 baseline, and AVX2 is checked above.
+            unsafe {
+                let mut x = a.clone();
+                x86::sse2::xor_assign(&mut x, &b);
+                assert_eq!(x, expect);
+                assert_eq!(x86::sse2::eq(&a, &b), scalar::eq(&a, &b));
+                assert!(x86::sse2::is_ones(&ones) && x86::sse2::is_zero(&vec![0; n]));
+                if avx2 {
+                    let mut x = a.clone();
+                    x86::avx2::and_not_assign(&mut x, &b);
+                    let mut y = a.clone();
+                    scalar::and_not_assign(&mut y, &b);
+                    assert_eq!(x, y);
+                    assert!(x86::avx2::eq(&a, &a) && x86::avx2::is_ones(&ones));
+                    assert_eq!(x86::avx2::is_zero(&a), scalar::is_zero(&a));
+                }
+            }
+        }
+    }
+}
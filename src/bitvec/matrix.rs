@@ -0,0 +1,300 @@
+use super::BitVec;
+use std::fmt::{self, Debug};
+
+/// A dense boolean matrix whose rows are stored as contiguous words.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    data: Vec<u64>,
+    rows: usize,
+    cols: usize,
+    row_words: usize,
+}
+
+impl BitMatrix {
+    /// Creates an all-zero `rows` x `cols` matrix.
+    #[inline]
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let row_words = cols.div_ceil(BitVec::WORD_SIZE);
+        Self {
+            data: vec![0; rows * row_words],
+            rows,
+            cols,
+            row_words,
+        }
+    }
+
+    #[inline]
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::new(n, n);
+        for i in 0..n {
+            m.set(i, i, true);
+        }
+        m
+    }
+
+    /// Creates a matrix from rows of equal length.
+    pub fn from_rows(rows: &[BitVec]) -> Self {
+        let cols = rows.first().map_or(0, |r| r.len());
+        let mut m = Self::new(rows.len(), cols);
+        for (i, r) in rows.iter().enumerate() {
+            assert!(r.len() == cols);
+            m.row_words_mut(i).copy_from_slice(r.words());
+        }
+        m
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn get(&self, r: usize, c: usize) -> bool {
+        debug_assert!(r < self.rows && c < self.cols);
+        (self.data[r * self.row_words + c / BitVec::WORD_SIZE] >> (c % BitVec::WORD_SIZE)) & 1 == 1
+    }
+
+    #[inline]
+    pub fn set(&mut self, r: usize, c: usize, val: bool) {
+        debug_assert!(r < self.rows && c < self.cols);
+        let w = &mut self.data[r * self.row_words + c / BitVec::WORD_SIZE];
+        let mask = 1 << (c % BitVec::WORD_SIZE);
+        if val {
+            *w |= mask;
+        } else {
+            *w &= !mask;
+        }
+    }
+
+    #[inline]
+    pub fn row_words(&self, r: usize) -> &[u64] {
+        &self.data[r * self.row_words..(r + 1) * self.row_words]
+    }
+
+    #[inline]
+    fn row_words_mut(&mut self, r: usize) -> &mut [u64] {
+        &mut self.data[r * self.row_words..(r + 1) * self.row_words]
+    }
+
+    #[inline]
+    pub fn row(&self, r: usize) -> BitVec {
+        BitVec::from_words(self.row_words(r), self.cols)
+    }
+
+    #[inline]
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        for k in 0..self.row_words {
+            self.data
+                .swap(a * self.row_words + k, b * self.row_words + k);
+        }
+    }
+
+    /// Applies `f` word by word to rows `dst` and `src`, storing the result in `dst`.
+    #[inline]
+    fn row_op(&mut self, dst: usize, src: usize, f: impl Fn(u64, u64) -> u64) {
+        let (d, s) = (dst * self.row_words, src * self.row_words);
+        for k in 0..self.row_words {
+            self.data[d + k] = f(self.data[d + k], self.data[s + k]);
+        }
+    }
+
+    /// `row[dst] ^= row[src]`.
+    #[inline]
+    pub fn xor_row(&mut self, dst: usize, src: usize) {
+        self.row_op(dst, src, |d, s| d ^ s);
+    }
+
+    /// `row[dst] &= row[src]`.
+    #[inline]
+    pub fn and_row(&mut self, dst: usize, src: usize) {
+        self.row_op(dst, src, |d, s| d & s);
+    }
+
+    /// `row[dst] |= row[src]`.
+    #[inline]
+    pub fn or_row(&mut self, dst: usize, src: usize) {
+        self.row_op(dst, src, |d, s| d | s);
+    }
+
+    pub fn transpose(&self) -> BitMatrix {
+        let mut t = BitMatrix::new(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in BitVec::from_words(self.row_words(r), self.cols).iter_ones() {
+                t.set(c, r, true);
+            }
+        }
+        t
+    }
+
+    /// Accumulates `row[k]` of `rhs` into row `i` of the product for every set bit
+    /// `(i, k)` of `self`, combining words with `f`.
+    #[inline]
+    fn product(&self, rhs: &BitMatrix, f: impl Fn(u64, u64) -> u64) -> BitMatrix {
+        assert!(self.cols == rhs.rows);
+        let mut res = BitMatrix::new(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            let row = BitVec::from_words(self.row_words(i), self.cols);
+            let dst = i * res.row_words;
+            for k in row.iter_ones() {
+                for (d, &s) in res.data[dst..dst + res.row_words]
+                    .iter_mut()
+                    .zip(rhs.row_words(k))
+                {
+                    *d = f(*d, s);
+                }
+            }
+        }
+        res
+    }
+
+    /// Matrix product over GF(2).
+    #[inline]
+    pub fn mul_gf2(&self, rhs: &BitMatrix) -> BitMatrix {
+        self.product(rhs, |d, s| d ^ s)
+    }
+
+    /// Matrix product over the boolean semiring `(or, and)`.
+    #[inline]
+    pub fn mul_bool(&self, rhs: &BitMatrix) -> BitMatrix {
+        self.product(rhs, |d, s| d | s)
+    }
+
+    /// Reduces the matrix in place to reduced row echelon form over GF(2) and returns
+    /// its rank. The first `rank` rows then form a basis of the row space.
+    pub fn gaussian_elimination(&mut self) -> usize {
+        let mut rank = 0;
+        for c in 0..self.cols {
+            let Some(p) = (rank..self.rows).find(|&r| self.get(r, c)) else {
+                continue;
+            };
+            self.swap_rows(rank, p);
+            for r in 0..self.rows {
+                if r != rank && self.get(r, c) {
+                    self.xor_row(r, rank);
+                }
+            }
+            rank += 1;
+        }
+        rank
+    }
+
+    /// Rank over GF(2).
+    #[inline]
+    pub fn rank(&self) -> usize {
+        self.clone().gaussian_elimination()
+    }
+
+    /// A basis of the row space over GF(2), in reduced row echelon form.
+    pub fn basis(&self) -> BitMatrix {
+        let mut m = self.clone();
+        let rank = m.gaussian_elimination();
+        m.data.truncate(rank * m.row_words);
+        m.rows = rank;
+        m
+    }
+
+    /// Transitive, non-reflexive closure of a square relation matrix, where bit
+    /// `(i, j)` means `i` reaches `j` in one step.
+    pub fn transitive_closure(&self) -> BitMatrix {
+        assert!(self.rows == self.cols);
+        let mut m = self.clone();
+        for k in 0..m.rows {
+            for i in 0..m.rows {
+                if m.get(i, k) {
+                    m.or_row(i, k);
+                }
+            }
+        }
+        m
+    }
+}
+
+impl Debug for BitMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.rows).map(|r| self.row(r)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    fn rand_matrix(rng: &mut StdRng, rows: usize, cols: usize, p: f64) -> BitMatrix {
+        let mut m = BitMatrix::new(rows, cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                m.set(r, c, rng.random_bool(p));
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_transpose_product() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (n, k, m) = (5, 70, 9);
+        let a = rand_matrix(&mut rng, n, k, 0.5);
+        let b = rand_matrix(&mut rng, k, m, 0.5);
+        let t = a.transpose();
+        assert_eq!((t.rows(), t.cols()), (k, n));
+        assert_eq!(t.transpose(), a);
+        let (x, o) = (a.mul_gf2(&b), a.mul_bool(&b));
+        for i in 0..n {
+            for j in 0..m {
+                let terms = (0..k).filter(|&l| a.get(i, l) && b.get(l, j)).count();
+                assert_eq!(x.get(i, j), terms % 2 == 1);
+                assert_eq!(o.get(i, j), terms > 0);
+            }
+        }
+        assert_eq!(a.mul_gf2(&BitMatrix::identity(k)), a);
+    }
+
+    #[test]
+    fn test_gaussian_elimination() {
+        let rows = [
+            BitVec::from("1100"),
+            BitVec::from("0110"),
+            BitVec::from("1010"),
+            BitVec::from("0001"),
+        ];
+        let m = BitMatrix::from_rows(&rows);
+        assert_eq!(m.rank(), 3);
+        let b = m.basis();
+        assert_eq!(b.rows(), 3);
+        assert_eq!(b.basis(), b);
+        assert_eq!(BitMatrix::identity(70).rank(), 70);
+        let mut rng = StdRng::seed_from_u64(1);
+        let a = rand_matrix(&mut rng, 20, 80, 0.5);
+        let b = a.basis();
+        // Every original row lies in the span of the basis: adding it keeps the rank.
+        for r in 0..a.rows() {
+            let mut rows: Vec<BitVec> = (0..b.rows()).map(|i| b.row(i)).collect();
+            rows.push(a.row(r));
+            assert_eq!(BitMatrix::from_rows(&rows).rank(), b.rows());
+        }
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let mut m = BitMatrix::new(4, 4);
+        m.set(0, 1, true);
+        m.set(1, 2, true);
+        m.set(2, 1, true);
+        let c = m.transitive_closure();
+        let reach = [(0, 1), (0, 2), (1, 1), (1, 2), (2, 1), (2, 2)];
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(c.get(i, j), reach.contains(&(i, j)), "i={i} j={j}");
+            }
+        }
+        assert_eq!(c.transitive_closure(), c);
+    }
+}
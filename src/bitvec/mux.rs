@@ -0,0 +1,92 @@
+use super::{BitVec, resolve_range};
+use std::ops::RangeBounds;
+
+impl BitVec {
+    /// Returns `a` if the one-bit `cond` is set and `b` otherwise.
+    #[inline]
+    pub fn ite(cond: &BitVec, a: &BitVec, b: &BitVec) -> BitVec {
+        assert!(a.len() == b.len());
+        if cond.bool() { a.clone() } else { b.clone() }
+    }
+
+    /// Bitwise select: takes each bit from `a` where `mask` is set and from `b` elsewhere.
+    #[inline]
+    pub fn mux(mask: &BitVec, a: &BitVec, b: &BitVec) -> BitVec {
+        assert!(mask.len() == a.len() && a.len() == b.len());
+        let mut res = b.clone();
+        for ((r, m), a) in res.words_mut().iter_mut().zip(mask.words()).zip(a.words()) {
+            *r = (m & a) | (!m & *r);
+        }
+        res
+    }
+
+    /// Overwrites the bits starting at `offset` with `other`, i.e.
+    /// `self[offset + other.len() - 1 : offset] = other`. The length is unchanged.
+    #[inline]
+    pub fn insert(&mut self, offset: usize, other: &BitVec) {
+        let end = offset + other.len();
+        assert!(end <= self.len());
+        let mut pos = offset;
+        while pos < end {
+            let (ws, bs) = (pos / Self::WORD_SIZE, pos % Self::WORD_SIZE);
+            let take = (Self::WORD_SIZE - bs).min(end - pos);
+            let mask = u64::MAX >> (Self::WORD_SIZE - take);
+            let src = other.word_at(pos - offset) & mask;
+            self.bits[ws] = (self.bits[ws] & !(mask << bs)) | (src << bs);
+            pos += take;
+        }
+    }
+
+    /// Overwrites the bits in `range` with `other`, which must have the same length
+    /// as the range.
+    #[inline]
+    pub fn replace_range<R: RangeBounds<usize>>(&mut self, range: R, other: &BitVec) {
+        let (l, h) = resolve_range(range, self.len());
+        assert!(l <= h && h - l == other.len());
+        self.insert(l, other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_ite_mux() {
+        let a = BitVec::from("1100");
+        let b = BitVec::from("1010");
+        assert_eq!(BitVec::ite(&BitVec::from(true), &a, &b), a);
+        assert_eq!(BitVec::ite(&BitVec::from(false), &a, &b), b);
+        let mask = BitVec::from("0110");
+        assert_eq!(BitVec::mux(&mask, &a, &b), BitVec::from("1100"));
+        let mask = BitVec::from("0011");
+        assert_eq!(BitVec::mux(&mask, &a, &b), BitVec::from("1000"));
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for w in [1, 63, 64, 65, 130, 200] {
+            let x = BitVec::from_iter((0..w).map(|_| rng.random::<bool>()));
+            for _ in 0..20 {
+                let l = rng.random_range(0..=w);
+                let h = rng.random_range(l..=w);
+                let y = BitVec::from_iter((l..h).map(|_| rng.random::<bool>()));
+                let mut r = x.clone();
+                r.replace_range(l..h, &y);
+                let expect = BitVec::from_iter(
+                    x.slice(..l)
+                        .iter()
+                        .chain(y.iter())
+                        .chain(x.slice(h..).iter()),
+                );
+                assert_eq!(r, expect, "w={w} l={l} h={h}");
+                assert_eq!(r.slice(l..h), y);
+            }
+        }
+        let mut x = BitVec::zero(8);
+        x.insert(2, &BitVec::from("111"));
+        assert_eq!(x, BitVec::from("00011100"));
+    }
+}
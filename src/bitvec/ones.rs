@@ -0,0 +1,132 @@
+use super::BitVec;
+
+impl BitVec {
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.words().iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len() - self.count_ones()
+    }
+
+    /// Index of the least significant set bit.
+    #[inline]
+    pub fn first_one(&self) -> Option<usize> {
+        self.words()
+            .iter()
+            .position(|&w| w != 0)
+            .map(|i| i * Self::WORD_SIZE + self.bits[i].trailing_zeros() as usize)
+    }
+
+    /// Index of the most significant set bit.
+    #[inline]
+    pub fn last_one(&self) -> Option<usize> {
+        self.words()
+            .iter()
+            .rposition(|&w| w != 0)
+            .map(|i| i * Self::WORD_SIZE + 63 - self.bits[i].leading_zeros() as usize)
+    }
+
+    /// Number of zero bits above the most significant set bit.
+    #[inline]
+    pub fn leading_zeros(&self) -> usize {
+        match self.last_one() {
+            Some(i) => self.len() - 1 - i,
+            None => self.len(),
+        }
+    }
+
+    /// Number of zero bits below the least significant set bit.
+    #[inline]
+    pub fn trailing_zeros(&self) -> usize {
+        self.first_one().unwrap_or(self.len())
+    }
+
+    /// Iterates over the indices of set bits in increasing order.
+    #[inline]
+    pub fn iter_ones(&self) -> IterOnes<'_> {
+        let words = self.words();
+        IterOnes {
+            words,
+            idx: 0,
+            cur: words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+pub struct IterOnes<'a> {
+    words: &'a [u64],
+    idx: usize,
+    cur: u64,
+}
+
+impl Iterator for IterOnes<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur == 0 {
+            self.idx += 1;
+            if self.idx >= self.words.len() {
+                return None;
+            }
+            self.cur = self.words[self.idx];
+        }
+        let bit = self.cur.trailing_zeros() as usize;
+        self.cur &= self.cur - 1;
+        Some(self.idx * BitVec::WORD_SIZE + bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest: usize = self
+            .words
+            .iter()
+            .skip(self.idx + 1)
+            .map(|w| w.count_ones() as usize)
+            .sum();
+        let len = rest + self.cur.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for IterOnes<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvec::words::Words;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_ones() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for w in [0, 1, 5, 63, 64, 65, 130, 256] {
+            for p in [0.0, 0.01, 0.5, 1.0] {
+                let x = BitVec::from_iter((0..w).map(|_| rng.random_bool(p)));
+                let ones: Vec<usize> = (0..w).filter(|&i| x.get(i)).collect();
+                assert_eq!(x.iter_ones().collect::<Vec<_>>(), ones);
+                assert_eq!(x.iter_ones().len(), ones.len());
+                assert_eq!(x.count_ones(), ones.len());
+                assert_eq!(x.count_zeros(), w - ones.len());
+                assert_eq!(x.first_one(), ones.first().copied());
+                assert_eq!(x.last_one(), ones.last().copied());
+                assert_eq!(x.trailing_zeros(), ones.first().copied().unwrap_or(w));
+                assert_eq!(x.leading_zeros(), ones.last().map_or(w, |&i| w - 1 - i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_ones_unnormalized() {
+        let b = BitVec {
+            bits: Words::from([1 << 63, 0]),
+            last_len: 0,
+        };
+        assert_eq!(b.len(), 64);
+        assert_eq!(b.leading_zeros(), 0);
+        assert_eq!(b.iter_ones().collect::<Vec<_>>(), [63]);
+    }
+}
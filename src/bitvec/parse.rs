@@ -0,0 +1,288 @@
+use super::BitVec;
+use super::words::Words;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBitVecError {
+    Empty,
+    InvalidRadix(u32),
+    InvalidDigit(char),
+    InvalidFormat,
+    /// The value does not fit in the requested width.
+    Overflow,
+}
+
+impl Display for ParseBitVecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBitVecError::Empty => write!(f, "cannot parse bit-vector from empty string"),
+            ParseBitVecError::InvalidRadix(r) => write!(f, "invalid radix {r}"),
+            ParseBitVecError::InvalidDigit(c) => write!(f, "invalid digit {c:?} in bit-vector"),
+            ParseBitVecError::InvalidFormat => write!(f, "invalid bit-vector literal"),
+            ParseBitVecError::Overflow => write!(f, "bit-vector literal does not fit in width"),
+        }
+    }
+}
+
+impl Error for ParseBitVecError {}
+
+impl BitVec {
+    /// Parses `s` in the given radix (2 to 36) into a bit-vector of exactly `width` bits.
+    /// A leading `-` denotes the two's complement of the magnitude, which then must fit
+    /// in `width` bits as a signed value. Underscores between digits are ignored.
+    pub fn from_str_radix(s: &str, radix: u32, width: usize) -> Result<Self, ParseBitVecError> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseBitVecError::InvalidRadix(radix));
+        }
+        let (neg, digits) = match s.strip_prefix('-') {
+            Some(d) => (true, d),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || digits.starts_with('_') {
+            return Err(ParseBitVecError::Empty);
+        }
+        let mut limbs: Vec<u64> = vec![0];
+        for c in digits.chars() {
+            if c == '_' {
+                continue;
+            }
+            let d = c.to_digit(radix).ok_or(ParseBitVecError::InvalidDigit(c))?;
+            let mut carry = d as u128;
+            for l in limbs.iter_mut() {
+                let t = *l as u128 * radix as u128 + carry;
+                *l = t as u64;
+                carry = t >> 64;
+            }
+            if carry != 0 {
+                limbs.push(carry as u64);
+            }
+        }
+        while limbs.len() > 1 && limbs[limbs.len() - 1] == 0 {
+            limbs.pop();
+        }
+        let top = limbs[limbs.len() - 1];
+        let bit_len = (limbs.len() - 1) * Self::WORD_SIZE + (64 - top.leading_zeros() as usize);
+        if neg && bit_len > 0 {
+            // The magnitude may be at most 2^(width - 1).
+            let is_pow2 = top.is_power_of_two() && limbs[..limbs.len() - 1].iter().all(|&l| l == 0);
+            if bit_len > width || (bit_len == width && !is_pow2) {
+                return Err(ParseBitVecError::Overflow);
+            }
+        } else if bit_len > width {
+            return Err(ParseBitVecError::Overflow);
+        }
+        if width == 0 {
+            return Ok(Self::new());
+        }
+        limbs.resize(width.div_ceil(Self::WORD_SIZE), 0);
+        let mut res = BitVec {
+            bits: Words::from(limbs),
+            last_len: 0,
+        };
+        res.set_len(width);
+        if neg {
+            res.neg_assign();
+        }
+        Ok(res)
+    }
+}
+
+/// Number of digits in `s`, ignoring `_` separators.
+#[inline]
+fn digit_count(s: &str) -> usize {
+    s.chars().filter(|&c| c != '_').count()
+}
+
+/// Like [`BitVec::from_str_radix`], but for literal forms that never take a sign.
+#[inline]
+fn from_unsigned(s: &str, radix: u32, width: usize) -> Result<BitVec, ParseBitVecError> {
+    match s.chars().next() {
+        Some(c @ ('-' | '+')) => Err(ParseBitVecError::InvalidDigit(c)),
+        _ => BitVec::from_str_radix(s, radix, width),
+    }
+}
+
+/// Parses a width given as plain decimal digits.
+#[inline]
+fn parse_width(s: &str) -> Result<usize, ParseBitVecError> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseBitVecError::InvalidFormat);
+    }
+    s.parse().map_err(|_| ParseBitVecError::InvalidFormat)
+}
+
+fn parse_verilog(s: &str) -> Result<BitVec, ParseBitVecError> {
+    let (neg, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s.trim_start()),
+        None => (false, s),
+    };
+    let (width, rest) = s.split_once('\'').ok_or(ParseBitVecError::InvalidFormat)?;
+    let width = if width.trim().is_empty() {
+        32
+    } else {
+        parse_width(width.trim())?
+    };
+    let rest = rest.strip_prefix(['s', 'S']).unwrap_or(rest);
+    let mut chars = rest.chars();
+    let radix = match chars.next() {
+        Some('b' | 'B') => 2,
+        Some('o' | 'O') => 8,
+        Some('d' | 'D') => 10,
+        Some('h' | 'H') => 16,
+        _ => return Err(ParseBitVecError::InvalidFormat),
+    };
+    let mut res = from_unsigned(chars.as_str().trim(), radix, width)?;
+    if neg {
+        res.neg_assign();
+    }
+    Ok(res)
+}
+
+fn parse_smt_indexed(s: &str) -> Result<BitVec, ParseBitVecError> {
+    let inner = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or(ParseBitVecError::InvalidFormat)?;
+    let mut parts = inner.split_whitespace();
+    if parts.next() != Some("_") {
+        return Err(ParseBitVecError::InvalidFormat);
+    }
+    let value = parts
+        .next()
+        .and_then(|v| v.strip_prefix("bv"))
+        .ok_or(ParseBitVecError::InvalidFormat)?;
+    let width = parse_width(parts.next().ok_or(ParseBitVecError::InvalidFormat)?)?;
+    if width == 0 || parts.next().is_some() {
+        return Err(ParseBitVecError::InvalidFormat);
+    }
+    // `(_ bvX n)` is `X mod 2^n`; a decimal digit takes fewer than four bits.
+    let mut res = from_unsigned(value, 10, digit_count(value) * 4)?;
+    res.resize(width, false);
+    Ok(res)
+}
+
+/// Parses bit-vector literals in one of the following forms:
+///
+/// - plain binary digits, most significant first: `0101`
+/// - SMT-LIB binary and hexadecimal: `#b0101`, `#x1f`
+/// - SMT-LIB indexed: `(_ bv13 8)`
+/// - Verilog sized literals: `8'hff`, `4'b1010`, `-8'sd3`, `'d7`
+///
+/// The width is taken from the literal itself. Only Verilog literals may be negated,
+/// with a leading `-` before the size; use [`BitVec::from_str_radix`] for signed decimal.
+impl FromStr for BitVec {
+    type Err = ParseBitVecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseBitVecError::Empty);
+        }
+        if let Some(d) = s.strip_prefix("#b") {
+            from_unsigned(d, 2, digit_count(d))
+        } else if let Some(d) = s.strip_prefix("#x") {
+            from_unsigned(d, 16, digit_count(d) * 4)
+        } else if s.starts_with('(') {
+            parse_smt_indexed(s)
+        } else if s.contains('\'') {
+            parse_verilog(s)
+        } else {
+            from_unsigned(s, 2, digit_count(s))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_radix() {
+        assert_eq!(BitVec::from_str_radix("255", 10, 8), Ok(BitVec::ones(8)));
+        assert_eq!(BitVec::from_str_radix("-1", 10, 70), Ok(BitVec::ones(70)));
+        assert_eq!(
+            BitVec::from_str_radix("-128", 10, 8),
+            Ok(BitVec::from_usize(8, 0x80))
+        );
+        assert_eq!(
+            BitVec::from_str_radix("-129", 10, 8),
+            Err(ParseBitVecError::Overflow)
+        );
+        assert_eq!(
+            BitVec::from_str_radix("256", 10, 8),
+            Err(ParseBitVecError::Overflow)
+        );
+        assert_eq!(
+            BitVec::from_str_radix("1_0000_0000", 16, 33),
+            Ok(BitVec::one(33) << 32)
+        );
+        assert_eq!(
+            BitVec::from_str_radix("12a", 10, 8),
+            Err(ParseBitVecError::InvalidDigit('a'))
+        );
+        assert_eq!(
+            BitVec::from_str_radix("", 10, 8),
+            Err(ParseBitVecError::Empty)
+        );
+        assert_eq!(
+            BitVec::from_str_radix("1", 1, 8),
+            Err(ParseBitVecError::InvalidRadix(1))
+        );
+        assert_eq!(BitVec::from_str_radix("0", 10, 0), Ok(BitVec::new()));
+        let big = BitVec::from_str_radix("340282366920938463463374607431768211455", 10, 128);
+        assert_eq!(big, Ok(BitVec::ones(128)));
+        let big = BitVec::from_str_radix("-340282366920938463463374607431768211455", 10, 130);
+        assert_eq!(big, Ok(-BitVec::ones(128).zext(2)));
+        let h = BitVec::from_str_radix("deadbeefcafebabe1234", 16, 80).unwrap();
+        assert_eq!(h.slice(0..16), BitVec::from_usize(16, 0x1234));
+        assert_eq!(h.slice(64..80), BitVec::from_usize(16, 0xdead));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let p = |s: &str| s.parse::<BitVec>();
+        assert_eq!(p("0101"), Ok(BitVec::from("0101")));
+        assert_eq!(p("#b0101"), Ok(BitVec::from("0101")));
+        assert_eq!(p("#x0f"), Ok(BitVec::from_usize(8, 0x0f)));
+        assert_eq!(p("(_ bv13 8)"), Ok(BitVec::from_usize(8, 13)));
+        assert_eq!(p("(_ bv256 8)"), Ok(BitVec::zero(8)));
+        assert_eq!(p("(_ bv259 8)"), Ok(BitVec::from_usize(8, 3)));
+        assert_eq!(p("(_ bv1 100)"), Ok(BitVec::one(100)));
+        assert_eq!(
+            p("(_ bv340282366920938463463374607431768211457 70)"),
+            Ok(BitVec::one(70))
+        );
+        assert_eq!(p("(_ bv0 0)"), Err(ParseBitVecError::InvalidFormat));
+        assert_eq!(p("8'hFF"), Ok(BitVec::ones(8)));
+        assert_eq!(p("4'b1010"), Ok(BitVec::from("1010")));
+        assert_eq!(p("-8'sd3"), Ok(BitVec::from_usize(8, 0xfd)));
+        assert_eq!(p("-4'b0001"), Ok(BitVec::ones(4)));
+        assert_eq!(p("'d7"), Ok(BitVec::from_usize(32, 7)));
+        assert_eq!(p("12'o7_7"), Ok(BitVec::from_usize(12, 0o77)));
+        assert_eq!(p("4'hFF"), Err(ParseBitVecError::Overflow));
+        assert_eq!(p("8'hxz"), Err(ParseBitVecError::InvalidDigit('x')));
+        assert_eq!(p("(_ bv1)"), Err(ParseBitVecError::InvalidFormat));
+        assert_eq!(p("012"), Err(ParseBitVecError::InvalidDigit('2')));
+        assert_eq!(p(""), Err(ParseBitVecError::Empty));
+    }
+
+    #[test]
+    fn test_from_str_rejects_sign() {
+        let p = |s: &str| s.parse::<BitVec>();
+        assert_eq!(p("#x-1"), Err(ParseBitVecError::InvalidDigit('-')));
+        assert_eq!(p("#b-1"), Err(ParseBitVecError::InvalidDigit('-')));
+        assert_eq!(p("#b+1"), Err(ParseBitVecError::InvalidDigit('+')));
+        assert_eq!(p("-1"), Err(ParseBitVecError::InvalidDigit('-')));
+        assert_eq!(p("+01"), Err(ParseBitVecError::InvalidDigit('+')));
+        assert_eq!(p("(_ bv-1 8)"), Err(ParseBitVecError::InvalidDigit('-')));
+        assert_eq!(p("(_ bv+1 8)"), Err(ParseBitVecError::InvalidDigit('+')));
+        assert_eq!(p("(_ bv1 +8)"), Err(ParseBitVecError::InvalidFormat));
+        assert_eq!(p("8'sd-3"), Err(ParseBitVecError::InvalidDigit('-')));
+        assert_eq!(p("8'h+f"), Err(ParseBitVecError::InvalidDigit('+')));
+        assert_eq!(p("+8'd3"), Err(ParseBitVecError::InvalidFormat));
+    }
+}
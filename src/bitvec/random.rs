@@ -0,0 +1,120 @@
+use super::BitVec;
+use rand::{Rng, RngExt};
+
+/// Fixed-point precision, in bits, of the one-probability used by [`BitVec::random_biased`].
+const BIAS_PRECISION: u32 = 16;
+
+impl BitVec {
+    /// Uniformly random bit-vector of exactly `width` bits.
+    #[inline]
+    pub fn random<R: Rng + ?Sized>(width: usize, rng: &mut R) -> Self {
+        let mut res = Self::zero(width);
+        for w in res.words_mut() {
+            *w = rng.random();
+        }
+        res.mask_last();
+        res
+    }
+
+    /// Random bit-vector of `width` bits where each bit is one with probability `p`,
+    /// accurate to 2^-16.
+    pub fn random_biased<R: Rng + ?Sized>(width: usize, p: f64, rng: &mut R) -> Self {
+        let q = (p.clamp(0.0, 1.0) * (1u64 << BIAS_PRECISION) as f64).round() as u64;
+        if q >= 1 << BIAS_PRECISION {
+            return Self::from_elem(width, true);
+        }
+        let mut res = Self::zero(width);
+        if q == 0 {
+            return res;
+        }
+        // Each step maps the one-probability x to (x + b) / 2 for the next bit b of q,
+        // starting from the least significant set bit.
+        let start = q.trailing_zeros();
+        for w in res.words_mut() {
+            let mut acc = 0u64;
+            for i in start..BIAS_PRECISION {
+                let r: u64 = rng.random();
+                acc = if (q >> i) & 1 == 1 { acc | r } else { acc & r };
+            }
+            *w = acc;
+        }
+        res.mask_last();
+        res
+    }
+
+    /// Random corner-case value of `width` bits: zero, one, all ones, the minimum or
+    /// maximum signed value, or a single set bit.
+    pub fn random_interesting<R: Rng + ?Sized>(width: usize, rng: &mut R) -> Self {
+        if width == 0 {
+            return Self::new();
+        }
+        match rng.random_range(0..6) {
+            0 => Self::zero(width),
+            1 => Self::one(width),
+            2 => Self::ones(width),
+            3 => {
+                let mut r = Self::zero(width);
+                r.set(width - 1, true);
+                r
+            }
+            4 => {
+                let mut r = Self::ones(width);
+                r.set(width - 1, false);
+                r
+            }
+            _ => {
+                let mut r = Self::zero(width);
+                r.set(rng.random_range(0..width), true);
+                r
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::SmallRng, rngs::StdRng};
+
+    #[test]
+    fn test_random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for w in [0, 1, 5, 64, 65, 130] {
+            let r = BitVec::random(w, &mut rng);
+            assert_eq!(r.len(), w);
+            assert_eq!(r, r.slice(..));
+        }
+        let mut small = SmallRng::seed_from_u64(0);
+        let r = BitVec::random(1000, &mut small);
+        assert!((400..600).contains(&r.count_ones()));
+    }
+
+    #[test]
+    fn test_random_biased() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let n = 100_000;
+        assert!(BitVec::random_biased(70, 0.0, &mut rng).is_zero());
+        assert!(BitVec::random_biased(70, 1.0, &mut rng).is_ones());
+        for p in [0.01, 0.25, 0.5, 0.9] {
+            let r = BitVec::random_biased(n, p, &mut rng);
+            assert_eq!(r.len(), n);
+            let f = r.count_ones() as f64 / n as f64;
+            assert!((f - p).abs() < 0.01, "p={p} f={f}");
+        }
+    }
+
+    #[test]
+    fn test_random_interesting() {
+        let mut rng = StdRng::seed_from_u64(2);
+        assert!(BitVec::random_interesting(0, &mut rng).is_empty());
+        for w in [1, 8, 100] {
+            for _ in 0..50 {
+                let r = BitVec::random_interesting(w, &mut rng);
+                assert_eq!(r.len(), w);
+                let min = BitVec::one(w) << (w - 1);
+                let max = BitVec::ones(w) >> 1;
+                assert!(r.is_zero() || r.is_ones() || r == min || r == max || r.count_ones() == 1);
+            }
+        }
+    }
+}
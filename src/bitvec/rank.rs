@@ -0,0 +1,143 @@
+use super::BitVec;
+
+/// Number of words per super-block.
+const SUPER_WORDS: usize = 8;
+
+/// Rank/select index over a [`BitVec`].
+///
+/// Stores the number of ones before every super-block of [`SUPER_WORDS`] words and,
+/// within each super-block, before every word, so `rank` is O(1) and `select` is a
+/// binary search over super-blocks followed by a scan of at most eight words.
+///
+/// The index takes ownership of the bit-vector; [`into_inner`](Self::into_inner)
+/// hands it back.
+#[derive(Clone, Debug)]
+pub struct RankSelect {
+    bv: BitVec,
+    supers: Vec<u64>,
+    blocks: Vec<u16>,
+}
+
+impl RankSelect {
+    pub fn new(bv: BitVec) -> Self {
+        let words = bv.words();
+        let mut supers = Vec::with_capacity(words.len().div_ceil(SUPER_WORDS) + 1);
+        let mut blocks = Vec::with_capacity(words.len());
+        let mut total = 0u64;
+        let mut local = 0u16;
+        for (i, w) in words.iter().enumerate() {
+            if i % SUPER_WORDS == 0 {
+                supers.push(total);
+                local = 0;
+            }
+            blocks.push(local);
+            local += w.count_ones() as u16;
+            total += w.count_ones() as u64;
+        }
+        supers.push(total);
+        Self { bv, supers, blocks }
+    }
+
+    #[inline]
+    pub fn bitvec(&self) -> &BitVec {
+        &self.bv
+    }
+
+    /// Drops the index and returns the bit-vector.
+    #[inline]
+    pub fn into_inner(self) -> BitVec {
+        self.bv
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bv.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bv.is_empty()
+    }
+
+    /// Total number of ones.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        *self.supers.last().unwrap() as usize
+    }
+
+    /// Number of ones strictly before position `i`, for `i <= len()`.
+    #[inline]
+    pub fn rank(&self, i: usize) -> usize {
+        assert!(i <= self.len());
+        let (w, b) = (i / BitVec::WORD_SIZE, i % BitVec::WORD_SIZE);
+        if w >= self.blocks.len() {
+            return self.count_ones();
+        }
+        let partial = (self.bv.bits[w] & ((1 << b) - 1)).count_ones() as usize;
+        self.supers[w / SUPER_WORDS] as usize + self.blocks[w] as usize + partial
+    }
+
+    /// Number of zeros strictly before position `i`, for `i <= len()`.
+    #[inline]
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank(i)
+    }
+
+    /// Position of the `k`-th one, counting from zero.
+    pub fn select(&self, k: usize) -> Option<usize> {
+        if k >= self.count_ones() {
+            return None;
+        }
+        let k = k as u64;
+        let s = self.supers.partition_point(|&c| c <= k) - 1;
+        let start = s * SUPER_WORDS;
+        let end = (start + SUPER_WORDS).min(self.blocks.len());
+        let local = (k - self.supers[s]) as u16;
+        let w = start + self.blocks[start..end].partition_point(|&c| c <= local) - 1;
+        let mut word = self.bv.bits[w];
+        for _ in 0..local - self.blocks[w] {
+            word &= word - 1;
+        }
+        Some(w * BitVec::WORD_SIZE + word.trailing_zeros() as usize)
+    }
+}
+
+impl From<BitVec> for RankSelect {
+    #[inline]
+    fn from(bv: BitVec) -> Self {
+        Self::new(bv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_rank_select() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for w in [0, 1, 63, 64, 65, 511, 512, 513, 2000] {
+            for p in [0.0, 0.05, 0.5, 1.0] {
+                let bv = BitVec::from_iter((0..w).map(|_| rng.random_bool(p)));
+                let rs = RankSelect::from(bv);
+                let bv = rs.bitvec();
+                let ones: Vec<usize> = bv.iter_ones().collect();
+                assert_eq!(rs.count_ones(), ones.len());
+                let mut r = 0;
+                for i in 0..=w {
+                    assert_eq!(rs.rank(i), r, "w={w} i={i}");
+                    assert_eq!(rs.rank0(i), i - r);
+                    if i < w && bv.get(i) {
+                        r += 1;
+                    }
+                }
+                for (k, &i) in ones.iter().enumerate() {
+                    assert_eq!(rs.select(k), Some(i), "w={w} k={k}");
+                }
+                assert_eq!(rs.select(ones.len()), None);
+                assert_eq!(rs.into_inner().iter_ones().collect::<Vec<_>>(), ones);
+            }
+        }
+    }
+}
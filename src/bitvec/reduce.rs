@@ -0,0 +1,83 @@
+use super::BitVec;
+
+impl BitVec {
+    /// AND of all bits; true for an empty bit-vector.
+    #[inline]
+    pub fn redand(&self) -> bool {
+        self.is_empty() || self.is_ones()
+    }
+
+    /// OR of all bits; false for an empty bit-vector.
+    #[inline]
+    pub fn redor(&self) -> bool {
+        self.words().iter().any(|&w| w != 0)
+    }
+
+    /// XOR of all bits; false for an empty bit-vector.
+    #[inline]
+    pub fn redxor(&self) -> bool {
+        self.words().iter().fold(0, |acc, &w| acc ^ w).count_ones() & 1 == 1
+    }
+
+    /// Returns true if `self` and `other` share a set bit.
+    #[inline]
+    pub fn intersects(&self, other: &BitVec) -> bool {
+        assert!(self.len() == other.len());
+        self.words()
+            .iter()
+            .zip(other.words())
+            .any(|(s, o)| s & o != 0)
+    }
+
+    /// Returns true if every bit set in `self` is also set in `other`.
+    #[inline]
+    pub fn is_subset_of(&self, other: &BitVec) -> bool {
+        assert!(self.len() == other.len());
+        self.words()
+            .iter()
+            .zip(other.words())
+            .all(|(s, o)| s & !o == 0)
+    }
+}
+
+impl From<bool> for BitVec {
+    /// Creates a 1-bit bit-vector, e.g. to wrap the result of a reduction.
+    #[inline]
+    fn from(b: bool) -> Self {
+        Self::from_elem(1, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_reduce() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for w in [0, 1, 5, 63, 64, 65, 130] {
+            for p in [0.0, 0.5, 1.0] {
+                let x = BitVec::from_iter((0..w).map(|_| rng.random_bool(p)));
+                assert_eq!(x.redand(), x.iter().all(|b| b));
+                assert_eq!(x.redor(), x.iter().any(|b| b));
+                assert_eq!(x.redxor(), x.iter().fold(false, |a, b| a ^ b));
+            }
+        }
+        assert_eq!(BitVec::from(true), BitVec::one(1));
+        assert_eq!(BitVec::from(false), BitVec::zero(1));
+    }
+
+    #[test]
+    fn test_set_queries() {
+        let a = BitVec::from_iter((0..130).map(|i| i % 6 == 0));
+        let b = BitVec::from_iter((0..130).map(|i| i % 3 == 0));
+        let c = BitVec::from_iter((0..130).map(|i| i % 3 == 1));
+        assert!(a.is_subset_of(&b));
+        assert!(!b.is_subset_of(&a));
+        assert!(a.intersects(&b));
+        assert!(!b.intersects(&c));
+        assert!(BitVec::zero(130).is_subset_of(&c));
+        assert!(!BitVec::zero(130).intersects(&BitVec::ones(130)));
+    }
+}
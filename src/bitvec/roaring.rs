@@ -0,0 +1,843 @@
+use super::BitVec;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::{
+    fmt::{self, Debug},
+    ops::{BitAnd, BitOr, BitXor, Sub},
+    slice,
+};
+
+/// Largest cardinality stored as a sorted array; denser containers become bitmaps.
+const ARRAY_MAX: usize = 4096;
+
+/// Number of words in a bitmap container, covering 2^16 values.
+const BITMAP_WORDS: usize = 1 << 16 >> 6;
+
+/// The low 16 bits of every id sharing the same high 16 bits.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum Container {
+    /// Sorted values, at most [`ARRAY_MAX`] of them.
+    Array(Vec<u16>),
+    /// More than [`ARRAY_MAX`] values as a bitmap of [`BITMAP_WORDS`] words.
+    Bitmap { card: u32, words: Vec<u64> },
+    /// Sorted, non-adjacent runs as `(start, length - 1)` pairs.
+    Run(Vec<(u16, u16)>),
+}
+
+impl Container {
+    /// Builds the smaller of an array or bitmap container from bitmap words.
+    fn from_words(words: Vec<u64>) -> Option<Self> {
+        let card: u32 = words.iter().map(|w| w.count_ones()).sum();
+        if card == 0 {
+            None
+        } else if card as usize <= ARRAY_MAX {
+            Some(Container::Array(BitmapIter::new(&words).collect()))
+        } else {
+            Some(Container::Bitmap { card, words })
+        }
+    }
+
+    #[inline]
+    fn from_sorted(v: Vec<u16>) -> Option<Self> {
+        debug_assert!(v.len() <= ARRAY_MAX);
+        (!v.is_empty()).then_some(Container::Array(v))
+    }
+
+    #[inline]
+    fn card(&self) -> usize {
+        match self {
+            Container::Array(a) => a.len(),
+            Container::Bitmap { card, .. } => *card as usize,
+            Container::Run(r) => r.iter().map(|&(_, l)| l as usize + 1).sum(),
+        }
+    }
+
+    #[inline]
+    fn contains(&self, v: u16) -> bool {
+        match self {
+            Container::Array(a) => a.binary_search(&v).is_ok(),
+            Container::Bitmap { words, .. } => (words[v as usize / 64] >> (v % 64)) & 1 == 1,
+            Container::Run(r) => {
+                let i = r.partition_point(|&(s, _)| s <= v);
+                i > 0 && v - r[i - 1].0 <= r[i - 1].1
+            }
+        }
+    }
+
+    #[inline]
+    fn max(&self) -> u16 {
+        match self {
+            Container::Array(a) => *a.last().unwrap(),
+            Container::Bitmap { words, .. } => {
+                let i = words.iter().rposition(|&w| w != 0).unwrap();
+                (i * 64 + 63 - words[i].leading_zeros() as usize) as u16
+            }
+            Container::Run(r) => {
+                let (s, l) = *r.last().unwrap();
+                s + l
+            }
+        }
+    }
+
+    /// The container as bitmap words.
+    fn words(&self) -> Vec<u64> {
+        if let Container::Bitmap { words, .. } = self {
+            return words.clone();
+        }
+        let mut words = vec![0; BITMAP_WORDS];
+        for v in self.iter() {
+            words[v as usize / 64] |= 1 << (v % 64);
+        }
+        words
+    }
+
+    /// Converts a run container back to an array or bitmap.
+    #[inline]
+    fn unrun(&mut self) {
+        if let Container::Run(_) = self {
+            *self = Container::from_words(self.words()).unwrap();
+        }
+    }
+
+    fn insert(&mut self, v: u16) -> bool {
+        self.unrun();
+        match self {
+            Container::Array(a) => match a.binary_search(&v) {
+                Ok(_) => false,
+                Err(i) => {
+                    a.insert(i, v);
+                    if a.len() > ARRAY_MAX {
+                        *self = Container::from_words(self.words()).unwrap();
+                    }
+                    true
+                }
+            },
+            Container::Bitmap { card, words } => {
+                let (w, m) = (v as usize / 64, 1 << (v % 64));
+                if words[w] & m != 0 {
+                    return false;
+                }
+                words[w] |= m;
+                *card += 1;
+                true
+            }
+            Container::Run(_) => unreachable!(),
+        }
+    }
+
+    fn remove(&mut self, v: u16) -> bool {
+        self.unrun();
+        match self {
+            Container::Array(a) => match a.binary_search(&v) {
+                Ok(i) => {
+                    a.remove(i);
+                    true
+                }
+                Err(_) => false,
+            },
+            Container::Bitmap { card, words } => {
+                let (w, m) = (v as usize / 64, 1 << (v % 64));
+                if words[w] & m == 0 {
+                    return false;
+                }
+                words[w] &= !m;
+                *card -= 1;
+                if *card as usize <= ARRAY_MAX {
+                    *self = Container::Array(BitmapIter::new(words).collect());
+                }
+                true
+            }
+            Container::Run(_) => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> ContainerIter<'_> {
+        match self {
+            Container::Array(a) => ContainerIter::Array(a.iter()),
+            Container::Bitmap { words, .. } => ContainerIter::Bitmap(BitmapIter::new(words)),
+            Container::Run(r) => ContainerIter::Run {
+                runs: r.iter(),
+                next: 0,
+                end: 0,
+            },
+        }
+    }
+
+    /// Combines two containers word by word with `f`.
+    #[inline]
+    fn zip_words(&self, other: &Container, f: impl Fn(u64, u64) -> u64) -> Option<Container> {
+        let mut words = self.words();
+        let other = other.words();
+        for (a, b) in words.iter_mut().zip(other) {
+            *a = f(*a, b);
+        }
+        Container::from_words(words)
+    }
+
+    fn union(&self, other: &Container) -> Option<Container> {
+        if let (Container::Array(a), Container::Array(b)) = (self, other)
+            && a.len() + b.len() <= ARRAY_MAX
+        {
+            let mut res = Vec::with_capacity(a.len() + b.len());
+            let (mut i, mut j) = (0, 0);
+            while i < a.len() && j < b.len() {
+                let (x, y) = (a[i], b[j]);
+                res.push(x.min(y));
+                i += (x <= y) as usize;
+                j += (y <= x) as usize;
+            }
+            res.extend_from_slice(&a[i..]);
+            res.extend_from_slice(&b[j..]);
+            return Container::from_sorted(res);
+        }
+        self.zip_words(other, |a, b| a | b)
+    }
+
+    fn intersect(&self, other: &Container) -> Option<Container> {
+        match (self, other) {
+            (Container::Array(a), o) | (o, Container::Array(a)) => {
+                Container::from_sorted(a.iter().copied().filter(|&v| o.contains(v)).collect())
+            }
+            _ => self.zip_words(other, |a, b| a & b),
+        }
+    }
+
+    fn difference(&self, other: &Container) -> Option<Container> {
+        match self {
+            Container::Array(a) => {
+                Container::from_sorted(a.iter().copied().filter(|&v| !other.contains(v)).collect())
+            }
+            _ => self.zip_words(other, |a, b| a & !b),
+        }
+    }
+
+    #[inline]
+    fn symmetric_difference(&self, other: &Container) -> Option<Container> {
+        self.zip_words(other, |a, b| a ^ b)
+    }
+
+    /// Switches to a run container if that is the smallest representation, and away
+    /// from one otherwise.
+    fn optimize(&mut self) {
+        let mut runs: Vec<(u16, u16)> = Vec::new();
+        for v in self.iter() {
+            match runs.last_mut() {
+                Some((s, l)) if *s as u32 + *l as u32 + 1 == v as u32 => *l += 1,
+                _ => runs.push((v, 0)),
+            }
+        }
+        let card = self.card();
+        let size = if card <= ARRAY_MAX {
+            card * 2
+        } else {
+            BITMAP_WORDS * 8
+        };
+        if runs.len() * 4 < size {
+            *self = Container::Run(runs);
+        } else {
+            self.unrun();
+        }
+    }
+
+    fn validate(&self) -> Result<(), &'static str> {
+        let valid = match self {
+            Container::Array(a) => {
+                !a.is_empty() && a.len() <= ARRAY_MAX && a.windows(2).all(|w| w[0] < w[1])
+            }
+            Container::Bitmap { card, words } => {
+                words.len() == BITMAP_WORDS
+                    && *card as usize > ARRAY_MAX
+                    && words.iter().map(|w| w.count_ones()).sum::<u32>() == *card
+            }
+            Container::Run(r) => {
+                !r.is_empty()
+                    && r.iter().all(|&(s, l)| s.checked_add(l).is_some())
+                    && r.windows(2)
+                        .all(|w| (w[0].0 as u32 + w[0].1 as u32 + 1) < w[1].0 as u32)
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err("invalid roaring bitmap container")
+        }
+    }
+}
+
+struct BitmapIter<'a> {
+    words: &'a [u64],
+    idx: usize,
+    cur: u64,
+}
+
+impl<'a> BitmapIter<'a> {
+    #[inline]
+    fn new(words: &'a [u64]) -> Self {
+        Self {
+            words,
+            idx: 0,
+            cur: words[0],
+        }
+    }
+}
+
+impl Iterator for BitmapIter<'_> {
+    type Item = u16;
+
+    #[inline]
+    fn next(&mut self) -> Option<u16> {
+        while self.cur == 0 {
+            self.idx += 1;
+            if self.idx >= self.words.len() {
+                return None;
+            }
+            self.cur = self.words[self.idx];
+        }
+        let bit = self.cur.trailing_zeros() as usize;
+        self.cur &= self.cur - 1;
+        Some((self.idx * 64 + bit) as u16)
+    }
+}
+
+enum ContainerIter<'a> {
+    Array(slice::Iter<'a, u16>),
+    Bitmap(BitmapIter<'a>),
+    Run {
+        runs: slice::Iter<'a, (u16, u16)>,
+        next: u32,
+        end: u32,
+    },
+}
+
+impl Iterator for ContainerIter<'_> {
+    type Item = u16;
+
+    #[inline]
+    fn next(&mut self) -> Option<u16> {
+        match self {
+            ContainerIter::Array(it) => it.next().copied(),
+            ContainerIter::Bitmap(it) => it.next(),
+            ContainerIter::Run { runs, next, end } => {
+                if *next >= *end {
+                    let &(s, l) = runs.next()?;
+                    *next = s as u32;
+                    *end = s as u32 + l as u32 + 1;
+                }
+                *next += 1;
+                Some((*next - 1) as u16)
+            }
+        }
+    }
+}
+
+/// A compressed set of `u32` ids in the style of roaring bitmaps.
+///
+/// Ids are grouped by their high 16 bits, and each group is stored as a sorted array,
+/// a bitmap or a list of runs. Array and bitmap containers switch automatically as
+/// the group grows or shrinks; run containers are chosen by [`optimize`](Self::optimize).
+#[derive(Clone, Default)]
+pub struct RoaringBitmap {
+    containers: Vec<(u16, Container)>,
+    len: usize,
+}
+
+impl RoaringBitmap {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn split(id: u32) -> (u16, u16) {
+        ((id >> 16) as u16, id as u16)
+    }
+
+    #[inline]
+    fn find(&self, hi: u16) -> Result<usize, usize> {
+        self.containers.binary_search_by_key(&hi, |(k, _)| *k)
+    }
+
+    /// Number of ids in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.containers.clear();
+        self.len = 0;
+    }
+
+    #[inline]
+    pub fn contains(&self, id: u32) -> bool {
+        let (hi, lo) = Self::split(id);
+        self.find(hi)
+            .is_ok_and(|i| self.containers[i].1.contains(lo))
+    }
+
+    /// Adds `id`. Returns true if it was not present.
+    pub fn insert(&mut self, id: u32) -> bool {
+        let (hi, lo) = Self::split(id);
+        let inserted = match self.find(hi) {
+            Ok(i) => self.containers[i].1.insert(lo),
+            Err(i) => {
+                self.containers.insert(i, (hi, Container::Array(vec![lo])));
+                true
+            }
+        };
+        self.len += inserted as usize;
+        inserted
+    }
+
+    /// Removes `id`. Returns true if it was present.
+    pub fn remove(&mut self, id: u32) -> bool {
+        let (hi, lo) = Self::split(id);
+        let Ok(i) = self.find(hi) else {
+            return false;
+        };
+        if !self.containers[i].1.remove(lo) {
+            return false;
+        }
+        if self.containers[i].1.card() == 0 {
+            self.containers.remove(i);
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Iterates over the ids in increasing order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.containers.iter().flat_map(|(k, c)| {
+            let base = (*k as u32) << 16;
+            c.iter().map(move |v| base | v as u32)
+        })
+    }
+
+    #[inline]
+    pub fn min(&self) -> Option<u32> {
+        self.iter().next()
+    }
+
+    #[inline]
+    pub fn max(&self) -> Option<u32> {
+        self.containers
+            .last()
+            .map(|(k, c)| ((*k as u32) << 16) | c.max() as u32)
+    }
+
+    /// Converts every container to its smallest representation, including runs.
+    pub fn optimize(&mut self) {
+        for (_, c) in self.containers.iter_mut() {
+            c.optimize();
+        }
+    }
+
+    /// Merges the containers of `other` into `self` by key, in place. Keys present in
+    /// both are combined with `f`; keys present in only one side are kept if the
+    /// matching flag is set. Containers of `self` that `f` does not touch are moved,
+    /// never copied.
+    fn merge_with(
+        &mut self,
+        other: &RoaringBitmap,
+        f: impl Fn(&Container, &Container) -> Option<Container>,
+        keep_self: bool,
+        keep_other: bool,
+    ) {
+        let b = &other.containers;
+        let mut extra = Vec::new();
+        let (mut w, mut j) = (0, 0);
+        for i in 0..self.containers.len() {
+            let k = self.containers[i].0;
+            while j < b.len() && b[j].0 < k {
+                if keep_other {
+                    extra.push(b[j].clone());
+                }
+                j += 1;
+            }
+            let card = self.containers[i].1.card();
+            let keep = if j < b.len() && b[j].0 == k {
+                j += 1;
+                self.len -= card;
+                match f(&self.containers[i].1, &b[j - 1].1) {
+                    Some(c) => {
+                        self.len += c.card();
+                        self.containers[i].1 = c;
+                        true
+                    }
+                    None => false,
+                }
+            } else {
+                if !keep_self {
+                    self.len -= card;
+                }
+                keep_self
+            };
+            if keep {
+                self.containers.swap(w, i);
+                w += 1;
+            }
+        }
+        self.containers.truncate(w);
+        if keep_other {
+            extra.extend(b[j..].iter().cloned());
+        }
+        if extra.is_empty() {
+            return;
+        }
+        // Merge the containers only in `other` in from the back.
+        self.len += extra.iter().map(|(_, c)| c.card()).sum::<usize>();
+        let n = self.containers.len();
+        self.containers
+            .resize_with(n + extra.len(), || (0, Container::Array(Vec::new())));
+        let (mut i, mut k) = (n, self.containers.len());
+        while let Some(e) = extra.pop() {
+            while i > 0 && self.containers[i - 1].0 > e.0 {
+                i -= 1;
+                k -= 1;
+                self.containers.swap(i, k);
+            }
+            k -= 1;
+            self.containers[k] = e;
+        }
+    }
+
+    #[inline]
+    pub fn union_with(&mut self, other: &RoaringBitmap) {
+        self.merge_with(other, Container::union, true, true);
+    }
+
+    #[inline]
+    pub fn intersect_with(&mut self, other: &RoaringBitmap) {
+        self.merge_with(other, Container::intersect, false, false);
+    }
+
+    #[inline]
+    pub fn difference_with(&mut self, other: &RoaringBitmap) {
+        self.merge_with(other, Container::difference, true, false);
+    }
+
+    #[inline]
+    pub fn symmetric_difference_with(&mut self, other: &RoaringBitmap) {
+        self.merge_with(other, Container::symmetric_difference, true, true);
+    }
+
+    /// Creates the set of indices of one bits in `bv`.
+    pub fn from_bitvec(bv: &BitVec) -> Self {
+        assert!(bv.len() as u64 <= 1 << 32);
+        let mut containers = Vec::new();
+        for (k, chunk) in bv.words().chunks(BITMAP_WORDS).enumerate() {
+            let mut words = chunk.to_vec();
+            words.resize(BITMAP_WORDS, 0);
+            if let Some(c) = Container::from_words(words) {
+                containers.push((k as u16, c));
+            }
+        }
+        let len = containers.iter().map(|(_, c)| c.card()).sum();
+        let mut res = RoaringBitmap { containers, len };
+        res.optimize();
+        res
+    }
+
+    /// Converts to a [`BitVec`] of `len` bits with bit `i` set iff `i` is in the set.
+    /// Panics if the set contains an id not below `len`.
+    pub fn to_bitvec(&self, len: usize) -> BitVec {
+        assert!(self.max().is_none_or(|m| (m as usize) < len));
+        let mut bv = BitVec::zero(len);
+        for (k, c) in self.containers.iter() {
+            let base = (*k as usize) << 16;
+            match c {
+                Container::Bitmap { words, .. } => {
+                    let dst = &mut bv.words_mut()[base / 64..];
+                    let n = dst.len().min(BITMAP_WORDS);
+                    dst[..n].copy_from_slice(&words[..n]);
+                }
+                Container::Run(r) => {
+                    for &(s, l) in r {
+                        let s = base + s as usize;
+                        bv.fill_range(s..=s + l as usize, true);
+                    }
+                }
+                Container::Array(a) => {
+                    for &v in a {
+                        bv.set(base + v as usize, true);
+                    }
+                }
+            }
+        }
+        bv
+    }
+
+    fn try_from_containers(containers: Vec<(u16, Container)>) -> Result<Self, &'static str> {
+        if !containers.windows(2).all(|w| w[0].0 < w[1].0) {
+            return Err("roaring bitmap container keys are not strictly increasing");
+        }
+        for (_, c) in containers.iter() {
+            c.validate()?;
+        }
+        let len = containers.iter().map(|(_, c)| c.card()).sum();
+        Ok(RoaringBitmap { containers, len })
+    }
+}
+
+impl From<&BitVec> for RoaringBitmap {
+    #[inline]
+    fn from(bv: &BitVec) -> Self {
+        Self::from_bitvec(bv)
+    }
+}
+
+impl Extend<u32> for RoaringBitmap {
+    #[inline]
+    fn extend<I: IntoIterator<Item = u32>>(&mut self, iter: I) {
+        for id in iter {
+            self.insert(id);
+        }
+    }
+}
+
+impl FromIterator<u32> for RoaringBitmap {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut s = Self::new();
+        s.extend(iter);
+        s
+    }
+}
+
+impl PartialEq for RoaringBitmap {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for RoaringBitmap {}
+
+impl Debug for RoaringBitmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+macro_rules! impl_roaring_op {
+    ($trait:ident, $method:ident, $with:ident) => {
+        impl $trait<&RoaringBitmap> for &RoaringBitmap {
+            type Output = RoaringBitmap;
+
+            #[inline]
+            fn $method(self, rhs: &RoaringBitmap) -> RoaringBitmap {
+                let mut res = self.clone();
+                res.$with(rhs);
+                res
+            }
+        }
+    };
+}
+
+impl_roaring_op!(BitAnd, bitand, intersect_with);
+impl_roaring_op!(BitOr, bitor, union_with);
+impl_roaring_op!(BitXor, bitxor, symmetric_difference_with);
+impl_roaring_op!(Sub, sub, difference_with);
+
+impl Serialize for RoaringBitmap {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.containers.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RoaringBitmap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let containers = Vec::<(u16, Container)>::deserialize(deserializer)?;
+        Self::try_from_containers(containers).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+    use std::collections::BTreeSet;
+
+    fn rand_set(rng: &mut StdRng, n: usize, range: u32) -> BTreeSet<u32> {
+        (0..n).map(|_| rng.random_range(0..range)).collect()
+    }
+
+    #[test]
+    fn test_roaring_ops() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (n, range) in [
+            (10, 1 << 20),
+            (5000, 1 << 16),
+            (20000, 1 << 17),
+            (3000, 5000),
+        ] {
+            let (ta, tb) = (rand_set(&mut rng, n, range), rand_set(&mut rng, n, range));
+            let a = RoaringBitmap::from_iter(ta.iter().copied());
+            let b = RoaringBitmap::from_iter(tb.iter().copied());
+            assert_eq!(a.len(), ta.len());
+            assert!(a.iter().eq(ta.iter().copied()));
+            assert_eq!(a.min(), ta.first().copied());
+            assert_eq!(a.max(), ta.last().copied());
+            let mut opt = a.clone();
+            opt.optimize();
+            let cases = [
+                (&a | &b, ta.union(&tb).copied().collect::<Vec<_>>()),
+                (&a & &b, ta.intersection(&tb).copied().collect()),
+                (&a - &b, ta.difference(&tb).copied().collect()),
+                (&a ^ &b, ta.symmetric_difference(&tb).copied().collect()),
+                (&opt & &b, ta.intersection(&tb).copied().collect()),
+                (&b - &opt, tb.difference(&ta).copied().collect()),
+            ];
+            for (r, expect) in cases {
+                assert_eq!(r.len(), expect.len());
+                assert!(r.iter().eq(expect.iter().copied()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_roaring_in_place() {
+        let words_ptr = |r: &RoaringBitmap, k: u16| {
+            let i = r.find(k).unwrap();
+            match &r.containers[i].1 {
+                Container::Bitmap { words, .. } => words.as_ptr(),
+                _ => panic!(),
+            }
+        };
+        // A bitmap container under key 2, untouched by every operation below.
+        let mut a: RoaringBitmap = (0..10000).map(|i| (2 << 16) | (i * 3)).collect();
+        a.extend([5, (4 << 16) | 1]);
+        let p = words_ptr(&a, 2);
+        let b: RoaringBitmap = [1, 3 << 16, (4 << 16) | 2, 6 << 16].into_iter().collect();
+        let expect: BTreeSet<u32> = a.iter().chain(b.iter()).collect();
+        a.union_with(&b);
+        assert_eq!(a.len(), expect.len());
+        assert!(a.iter().eq(expect.iter().copied()));
+        assert_eq!(words_ptr(&a, 2), p);
+        a.difference_with(&b);
+        assert_eq!(a.len(), 10002);
+        assert_eq!(words_ptr(&a, 2), p);
+        a.intersect_with(&(0..4 << 16).collect());
+        assert_eq!(a.len(), 10001);
+        assert!(a.find(4).is_err());
+    }
+
+    #[test]
+    fn test_roaring_containers() {
+        let mut r = RoaringBitmap::new();
+        for i in 0..=ARRAY_MAX as u32 {
+            assert!(r.insert(i * 2));
+        }
+        assert!(matches!(r.containers[0].1, Container::Bitmap { .. }));
+        assert!(r.remove(0));
+        assert!(!r.remove(1));
+        assert!(matches!(r.containers[0].1, Container::Array(_)));
+        let mut r: RoaringBitmap = (100..60000).collect();
+        r.optimize();
+        assert!(matches!(r.containers[0].1, Container::Run(_)));
+        assert!(r.contains(100) && r.contains(59999) && !r.contains(99));
+        assert!(r.insert(70000) && r.insert(50));
+        assert!(r.remove(1000));
+        assert_eq!(r.len(), 59900 + 1);
+        assert!(
+            r.iter().eq([50]
+                .into_iter()
+                .chain(100..1000)
+                .chain(1001..60000)
+                .chain([70000]))
+        );
+        for i in [50, 70000] {
+            r.remove(i);
+        }
+        r.insert(1000);
+        assert_eq!(r, (100..60000).collect());
+    }
+
+    #[test]
+    fn test_roaring_bitvec() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for (w, p) in [
+            (0, 0.5),
+            (100, 0.5),
+            (70000, 0.01),
+            (200000, 0.5),
+            (150000, 1.0),
+        ] {
+            let bv = BitVec::random_biased(w, p, &mut rng);
+            let r = RoaringBitmap::from(&bv);
+            assert_eq!(r.len(), bv.count_ones());
+            assert!(r.iter().map(|i| i as usize).eq(bv.iter_ones()));
+            assert_eq!(r.to_bitvec(w), bv);
+        }
+    }
+
+    #[test]
+    fn test_roaring_validate() {
+        let ok = vec![
+            (1, Container::Array(vec![1, 2])),
+            (3, Container::Run(vec![(5, 2)])),
+        ];
+        let r = RoaringBitmap::try_from_containers(ok).unwrap();
+        assert!(r.iter().eq([65537, 65538, 196613, 196614, 196615]));
+        let bad = [
+            vec![
+                (3, Container::Array(vec![1])),
+                (1, Container::Array(vec![2])),
+            ],
+            vec![(0, Container::Array(vec![2, 1]))],
+            vec![(0, Container::Array(vec![]))],
+            vec![(0, Container::Run(vec![(0, 3), (4, 1)]))],
+            vec![(0, Container::Run(vec![(65535, 1)]))],
+            vec![(
+                0,
+                Container::Bitmap {
+                    card: 1,
+                    words: vec![1; BITMAP_WORDS],
+                },
+            )],
+        ];
+        for c in bad {
+            assert!(RoaringBitmap::try_from_containers(c).is_err());
+        }
+    }
+
+    #[test]
+    fn test_roaring_serde() {
+        let mut r: RoaringBitmap = (0..10000).map(|i| i * 3).collect();
+        r.extend((1 << 16..(1 << 16) + 5000).chain([7 << 16, u32::MAX]));
+        r.optimize();
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(serde_json::from_str::<RoaringBitmap>(&json).unwrap(), r);
+        let bytes = bincode::serialize(&r).unwrap();
+        let d = bincode::deserialize::<RoaringBitmap>(&bytes).unwrap();
+        assert_eq!(d.len(), r.len());
+        assert_eq!(d, r);
+        assert_eq!(
+            serde_json::from_str::<RoaringBitmap>(r#"[[1,{"Array":[1,2]}],[3,{"Run":[[5,2]]}]]"#)
+                .unwrap(),
+            [65537, 65538, 196613, 196614, 196615].into_iter().collect()
+        );
+        for bad in [
+            r#"[[3,{"Array":[1]}],[1,{"Array":[2]}]]"#,
+            r#"[[0,{"Array":[2,1]}]]"#,
+            r#"[[0,{"Array":[]}]]"#,
+            r#"[[0,{"Run":[[65535,1]]}]]"#,
+            r#"[[0,{"Bitmap":{"card":5000,"words":[1,2,3]}}]]"#,
+            r#"[[0,{"Other":[]}]]"#,
+            r#"[[70000,{"Array":[1]}]]"#,
+        ] {
+            assert!(serde_json::from_str::<RoaringBitmap>(bad).is_err(), "{bad}");
+        }
+        let bad = bincode::serialize(&[(0u16, Container::Array(vec![2, 1]))]).unwrap();
+        assert!(bincode::deserialize::<RoaringBitmap>(&bad).is_err());
+        assert!(bincode::deserialize::<RoaringBitmap>(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
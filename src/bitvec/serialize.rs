@@ -0,0 +1,287 @@
+//! Serde support for [`BitVec`].
+//!
+//! A bit-vector is serialized as a struct with a `width` and a `value`. For
+//! human-readable formats the value is a hex string of exactly `ceil(width / 4)`
+//! digits, most significant first; for binary formats it is `ceil(width / 8)`
+//! little-endian bytes. Deserialization rejects values of any other length and values
+//! that do not fit in `width`.
+
+use super::BitVec;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+};
+use std::fmt;
+
+const FIELDS: &[&str] = &["width", "value"];
+
+impl BitVec {
+    #[inline]
+    fn hex_value(&self) -> String {
+        self.radix_digits(4, false, false)
+    }
+
+    fn from_hex_value(width: usize, s: &str) -> Result<Self, String> {
+        if !s.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("invalid hex value {s:?}"));
+        }
+        if s.len() != width.div_ceil(4) {
+            return Err(format!(
+                "expected {} hex digits for width {width}, found {}",
+                width.div_ceil(4),
+                s.len()
+            ));
+        }
+        if s.chars().all(|c| c == '0') {
+            return Ok(Self::zero(width));
+        }
+        Self::from_str_radix(s, 16, width).map_err(|e| e.to_string())
+    }
+
+    fn from_byte_value(width: usize, bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != width.div_ceil(8) {
+            return Err(format!(
+                "expected {} bytes for width {width}, found {}",
+                width.div_ceil(8),
+                bytes.len()
+            ));
+        }
+        if !width.is_multiple_of(8) && bytes[bytes.len() - 1] >> (width % 8) != 0 {
+            return Err(format!("value does not fit in width {width}"));
+        }
+        Ok(Self::from_le_bytes(bytes, width))
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ByteBufVisitor;
+
+        impl<'de> Visitor<'de> for ByteBufVisitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a byte array")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+                let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(b) = seq.next_element()? {
+                    v.push(b);
+                }
+                Ok(ByteBuf(v))
+            }
+        }
+
+        deserializer.deserialize_bytes(ByteBufVisitor)
+    }
+}
+
+impl Serialize for BitVec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let human_readable = serializer.is_human_readable();
+        let mut s = serializer.serialize_struct("BitVec", 2)?;
+        s.serialize_field("width", &self.len())?;
+        if human_readable {
+            s.serialize_field("value", &self.hex_value())?;
+        } else {
+            s.serialize_field("value", &Bytes(&self.to_le_bytes()))?;
+        }
+        s.end()
+    }
+}
+
+struct BitVecVisitor {
+    human_readable: bool,
+}
+
+impl BitVecVisitor {
+    fn build<E: de::Error>(&self, width: usize, value: Value) -> Result<BitVec, E> {
+        match value {
+            Value::Hex(s) if self.human_readable => BitVec::from_hex_value(width, &s),
+            Value::Bytes(b) if !self.human_readable => BitVec::from_byte_value(width, &b.0),
+            _ => unreachable!(),
+        }
+        .map_err(E::custom)
+    }
+
+    fn next_value<'de, A: MapAccess<'de>>(&self, map: &mut A) -> Result<Value, A::Error> {
+        Ok(if self.human_readable {
+            Value::Hex(map.next_value()?)
+        } else {
+            Value::Bytes(map.next_value()?)
+        })
+    }
+
+    fn next_element<'de, A: SeqAccess<'de>>(&self, seq: &mut A) -> Result<Option<Value>, A::Error> {
+        Ok(if self.human_readable {
+            seq.next_element()?.map(Value::Hex)
+        } else {
+            seq.next_element()?.map(Value::Bytes)
+        })
+    }
+}
+
+enum Value {
+    Hex(String),
+    Bytes(ByteBuf),
+}
+
+impl<'de> Visitor<'de> for BitVecVisitor {
+    type Value = BitVec;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("struct BitVec")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BitVec, A::Error> {
+        let width = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let value = self
+            .next_element(&mut seq)?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        self.build(width, value)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<BitVec, A::Error> {
+        let mut width = None;
+        let mut value = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "width" if width.is_none() => width = Some(map.next_value()?),
+                "value" if value.is_none() => value = Some(self.next_value(&mut map)?),
+                "width" | "value" => {
+                    return Err(de::Error::custom(format!("duplicate field `{key}`")));
+                }
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+        let width = width.ok_or_else(|| de::Error::missing_field("width"))?;
+        let value = value.ok_or_else(|| de::Error::missing_field("value"))?;
+        self.build(width, value)
+    }
+}
+
+impl<'de> Deserialize<'de> for BitVec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_struct("BitVec", FIELDS, BitVecVisitor { human_readable })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_value() {
+        for w in [0usize, 1, 4, 5, 64, 70] {
+            let bv = BitVec::from_iter((0..w).map(|i| i % 3 == 0));
+            let s = bv.hex_value();
+            assert_eq!(s.len(), w.div_ceil(4));
+            assert_eq!(BitVec::from_hex_value(w, &s), Ok(bv));
+        }
+        assert_eq!(BitVec::from_usize(8, 0x0f).hex_value(), "0f");
+        assert!(BitVec::from_hex_value(4, "1f").is_err());
+        assert!(BitVec::from_hex_value(8, "-1").is_err());
+        assert!(BitVec::from_hex_value(8, "0x1").is_err());
+        assert!(BitVec::from_hex_value(4, "").is_err());
+        assert!(BitVec::from_hex_value(4, "00a").is_err());
+        assert!(BitVec::from_hex_value(8, "f").is_err());
+    }
+
+    #[test]
+    fn test_byte_value() {
+        for w in [0usize, 1, 8, 9, 64, 70, 130] {
+            let bv = BitVec::from_iter((0..w).map(|i| i % 3 == 0));
+            let b = bv.to_le_bytes();
+            assert_eq!(b.len(), w.div_ceil(8));
+            assert_eq!(BitVec::from_byte_value(w, &b), Ok(bv));
+        }
+        assert!(BitVec::from_byte_value(4, &[0x1f]).is_err());
+        assert!(BitVec::from_byte_value(9, &[0xff]).is_err());
+        assert_eq!(BitVec::from_byte_value(9, &[0xff, 1]), Ok(BitVec::ones(9)));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        use serde::de::{IntoDeserializer, value::Error};
+        let d = vec!["101".to_string()].into_deserializer();
+        let r: Result<BitVec, Error> = BitVec::deserialize(d);
+        assert!(r.is_err());
+        let d = Vec::<u64>::new().into_deserializer();
+        let r: Result<BitVec, Error> = BitVec::deserialize(d);
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_json() {
+        for w in [0usize, 1, 4, 5, 64, 70, 130] {
+            let bv = BitVec::from_iter((0..w).map(|i| i % 3 == 0));
+            let json = serde_json::to_string(&bv).unwrap();
+            assert_eq!(
+                json,
+                format!(r#"{{"width":{w},"value":"{}"}}"#, bv.hex_value())
+            );
+            assert_eq!(serde_json::from_str::<BitVec>(&json).unwrap(), bv);
+        }
+        let p = |s: &str| serde_json::from_str::<BitVec>(s);
+        assert_eq!(p(r#"[8,"0f"]"#).unwrap(), BitVec::from_usize(8, 0x0f));
+        assert_eq!(
+            p(r#"{"value":"1f","width":5}"#).unwrap(),
+            BitVec::from_usize(5, 0x1f)
+        );
+        for bad in [
+            r#"{"width":4,"value":""}"#,
+            r#"{"width":4,"value":"00a"}"#,
+            r#"{"width":5,"value":"3f"}"#,
+            r#"{"width":8,"value":"-1"}"#,
+            r#"{"width":8}"#,
+            r#"{"width":8,"value":"00","width":8}"#,
+            r#"{"width":8,"value":"00","extra":0}"#,
+            r#"[8]"#,
+        ] {
+            assert!(p(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_bincode() {
+        for w in [0usize, 1, 8, 9, 64, 70, 130] {
+            let bv = BitVec::from_iter((0..w).map(|i| i % 3 == 0));
+            let bytes = bincode::serialize(&bv).unwrap();
+            // The width, the byte length, then the packed bytes.
+            assert_eq!(bytes.len(), 16 + w.div_ceil(8));
+            assert_eq!(bincode::deserialize::<BitVec>(&bytes).unwrap(), bv);
+        }
+        let p = |width: u64, value: &[u8]| {
+            let bytes = bincode::serialize(&(width, Bytes(value))).unwrap();
+            bincode::deserialize::<BitVec>(&bytes)
+        };
+        assert_eq!(p(9, &[0xff, 1]).unwrap(), BitVec::ones(9));
+        assert!(p(9, &[0xff]).is_err());
+        assert!(p(9, &[0xff, 3]).is_err());
+        assert!(p(4, &[0, 0]).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use super::BitVec;
+use std::ops::{Shl, ShlAssign, Shr, ShrAssign};
+
+impl BitVec {
+    #[inline]
+    fn shl_in_place(&mut self, n: usize) {
+        if n >= self.len() {
+            self.words_mut().fill(0);
+            return;
+        }
+        let wl = self.word_len();
+        let (ws, bs) = (n / Self::WORD_SIZE, n % Self::WORD_SIZE);
+        for i in (ws..wl).rev() {
+            let mut w = self.bits[i - ws] << bs;
+            if bs > 0 && i > ws {
+                w |= self.bits[i - ws - 1] >> (Self::WORD_SIZE - bs);
+            }
+            self.bits[i] = w;
+        }
+        self.words_mut()[..ws].fill(0);
+        self.mask_last();
+    }
+
+    #[inline]
+    fn shr_in_place(&mut self, n: usize) {
+        if n >= self.len() {
+            self.words_mut().fill(0);
+            return;
+        }
+        let wl = self.word_len();
+        let (ws, bs) = (n / Self::WORD_SIZE, n % Self::WORD_SIZE);
+        for i in 0..wl - ws {
+            let mut w = self.bits[i + ws] >> bs;
+            if bs > 0 && i + ws + 1 < wl {
+                w |= self.bits[i + ws + 1] << (Self::WORD_SIZE - bs);
+            }
+            self.bits[i] = w;
+        }
+        self.words_mut()[wl - ws..].fill(0);
+    }
+
+    /// Interprets `self` as an unsigned shift amount, saturating at `usize::MAX`.
+    #[inline]
+    fn shift_amount(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        if self.words()[1..].iter().any(|&w| w != 0) {
+            return usize::MAX;
+        }
+        usize::try_from(self.bits[0]).unwrap_or(usize::MAX)
+    }
+
+    /// Arithmetic shift right by `n`, filling with the sign bit.
+    #[inline]
+    pub fn ashr(&self, n: usize) -> BitVec {
+        let mut res = self.clone();
+        if self.is_empty() {
+            return res;
+        }
+        res.shr_in_place(n);
+        if self.sign_bit() {
+            res.fill_high(n);
+        }
+        res
+    }
+
+    /// Arithmetic shift right by an amount given as an unsigned bit-vector.
+    #[inline]
+    pub fn ashr_by(&self, amount: &BitVec) -> BitVec {
+        self.ashr(amount.shift_amount())
+    }
+
+    #[inline]
+    pub fn rotate_left(&self, n: usize) -> BitVec {
+        if self.is_empty() {
+            return self.clone();
+        }
+        let n = n % self.len();
+        if n == 0 {
+            return self.clone();
+        }
+        (self << n) | (self >> (self.len() - n))
+    }
+
+    #[inline]
+    pub fn rotate_right(&self, n: usize) -> BitVec {
+        if self.is_empty() {
+            return self.clone();
+        }
+        self.rotate_left(self.len() - n % self.len())
+    }
+}
+
+macro_rules! impl_shift {
+    (
+        trait = $trait:ident,
+        method = $method:ident,
+        assign_trait = $assign_trait:ident,
+        assign_method = $assign_method:ident,
+        in_place = $in_place:ident,
+    ) => {
+        impl $trait<usize> for BitVec {
+            type Output = BitVec;
+
+            #[inline]
+            fn $method(mut self, rhs: usize) -> Self::Output {
+                self.$in_place(rhs);
+                self
+            }
+        }
+
+        impl $trait<usize> for &BitVec {
+            type Output = BitVec;
+
+            #[inline]
+            fn $method(self, rhs: usize) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl $trait<&BitVec> for BitVec {
+            type Output = BitVec;
+
+            #[inline]
+            fn $method(self, rhs: &BitVec) -> Self::Output {
+                self.$method(rhs.shift_amount())
+            }
+        }
+
+        impl $trait<&BitVec> for &BitVec {
+            type Output = BitVec;
+
+            #[inline]
+            fn $method(self, rhs: &BitVec) -> Self::Output {
+                self.clone().$method(rhs.shift_amount())
+            }
+        }
+
+        impl $assign_trait<usize> for BitVec {
+            #[inline]
+            fn $assign_method(&mut self, rhs: usize) {
+                self.$in_place(rhs);
+            }
+        }
+
+        impl $assign_trait<&BitVec> for BitVec {
+            #[inline]
+            fn $assign_method(&mut self, rhs: &BitVec) {
+                self.$in_place(rhs.shift_amount());
+            }
+        }
+    };
+}
+
+impl_shift!(
+    trait = Shl,
+    method = shl,
+    assign_trait = ShlAssign,
+    assign_method = shl_assign,
+    in_place = shl_in_place,
+);
+
+impl_shift!(
+    trait = Shr,
+    method = shr,
+    assign_trait = ShrAssign,
+    assign_method = shr_assign,
+    in_place = shr_in_place,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_shift() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for w in [1, 3, 63, 64, 65, 128, 130, 200] {
+            let x = BitVec::from_iter((0..w).map(|_| rng.random::<bool>()));
+            for n in [0, 1, 5, 63, 64, 65, 127, 128, 129, w - 1, w, w + 1, 1000] {
+                let shl = &x << n;
+                let shr = &x >> n;
+                let ashr = x.ashr(n);
+                for i in 0..w {
+                    assert_eq!(shl.get(i), i >= n && x.get(i - n), "w={w} n={n} i={i}");
+                    assert_eq!(shr.get(i), i + n < w && x.get(i + n), "w={w} n={n} i={i}");
+                    let a = if i + n < w {
+                        x.get(i + n)
+                    } else {
+                        x.sign_bit()
+                    };
+                    assert_eq!(ashr.get(i), a, "w={w} n={n} i={i}");
+                }
+                let rl = x.rotate_left(n);
+                let rr = x.rotate_right(n);
+                for i in 0..w {
+                    assert_eq!(rl.get((i + n) % w), x.get(i));
+                    assert_eq!(rr.get(i), x.get((i + n) % w));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift_by_bitvec() {
+        let x = BitVec::from_iter((0..130).map(|i| i % 3 == 0));
+        let amt = BitVec::from_usize(8, 70);
+        assert!(&x << &amt == &x << 70);
+        assert!(&x >> &amt == &x >> 70);
+        assert!(x.ashr_by(&amt) == x.ashr(70));
+        let big = BitVec::from_usize(8, 200);
+        assert!((&x << &big).is_zero());
+        assert!((&x >> &big).is_zero());
+        let mut huge = BitVec::zero(130);
+        huge.set(129, true);
+        assert!((&x << &huge).is_zero());
+        let mut neg = BitVec::ones(130);
+        neg >>= &big;
+        assert!(neg.is_zero());
+        assert!(BitVec::ones(130).ashr_by(&huge).is_ones());
+    }
+}
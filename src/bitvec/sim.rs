@@ -0,0 +1,266 @@
+use super::BitVec;
+use ahash::RandomState;
+use rand::{Rng, RngExt};
+
+/// Simulation values of a set of signals under `64 * num_words` patterns.
+///
+/// Each signal owns `num_words` contiguous words, and bit `j` of word `k` is the
+/// value of the signal under pattern `64 * k + j`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimVec {
+    data: Vec<u64>,
+    num_signals: usize,
+    num_words: usize,
+}
+
+impl SimVec {
+    /// Creates `num_signals` all-zero signals of `num_words` words each.
+    #[inline]
+    pub fn new(num_signals: usize, num_words: usize) -> Self {
+        Self {
+            data: vec![0; num_signals * num_words],
+            num_signals,
+            num_words,
+        }
+    }
+
+    #[inline]
+    pub fn num_signals(&self) -> usize {
+        self.num_signals
+    }
+
+    #[inline]
+    pub fn num_words(&self) -> usize {
+        self.num_words
+    }
+
+    #[inline]
+    pub fn num_patterns(&self) -> usize {
+        self.num_words * BitVec::WORD_SIZE
+    }
+
+    /// Appends an all-zero signal and returns its index.
+    #[inline]
+    pub fn push_zero(&mut self) -> usize {
+        let s = self.num_signals;
+        self.data.resize(self.data.len() + self.num_words, 0);
+        self.num_signals += 1;
+        s
+    }
+
+    /// Appends a uniformly random signal and returns its index.
+    #[inline]
+    pub fn push_random<R: Rng + ?Sized>(&mut self, rng: &mut R) -> usize {
+        let s = self.push_zero();
+        self.randomize(s, rng);
+        s
+    }
+
+    #[inline]
+    pub fn signal(&self, s: usize) -> &[u64] {
+        &self.data[s * self.num_words..(s + 1) * self.num_words]
+    }
+
+    #[inline]
+    pub fn signal_mut(&mut self, s: usize) -> &mut [u64] {
+        &mut self.data[s * self.num_words..(s + 1) * self.num_words]
+    }
+
+    #[inline]
+    pub fn get(&self, s: usize, pattern: usize) -> bool {
+        let w = self.signal(s)[pattern / BitVec::WORD_SIZE];
+        (w >> (pattern % BitVec::WORD_SIZE)) & 1 == 1
+    }
+
+    #[inline]
+    pub fn set(&mut self, s: usize, pattern: usize, val: bool) {
+        let w = &mut self.signal_mut(s)[pattern / BitVec::WORD_SIZE];
+        let mask = 1 << (pattern % BitVec::WORD_SIZE);
+        if val {
+            *w |= mask;
+        } else {
+            *w &= !mask;
+        }
+    }
+
+    #[inline]
+    pub fn randomize<R: Rng + ?Sized>(&mut self, s: usize, rng: &mut R) {
+        for w in self.signal_mut(s) {
+            *w = rng.random();
+        }
+    }
+
+    /// Sets signal `s` to the constant `val` under every pattern.
+    #[inline]
+    pub fn fill(&mut self, s: usize, val: bool) {
+        self.signal_mut(s).fill(if val { u64::MAX } else { 0 });
+    }
+
+    /// Applies `f` word by word to `srcs` and stores the result in `dst`, which
+    /// may alias any of the sources.
+    #[inline]
+    fn apply<const N: usize>(&mut self, dst: usize, srcs: [usize; N], f: impl Fn([u64; N]) -> u64) {
+        let nw = self.num_words;
+        let (d, srcs) = (dst * nw, srcs.map(|s| s * nw));
+        for k in 0..nw {
+            self.data[d + k] = f(srcs.map(|s| self.data[s + k]));
+        }
+    }
+
+    /// `dst = !a`.
+    #[inline]
+    pub fn not(&mut self, dst: usize, a: usize) {
+        self.apply(dst, [a], |[a]| !a);
+    }
+
+    /// `dst = a & b`.
+    #[inline]
+    pub fn and(&mut self, dst: usize, a: usize, b: usize) {
+        self.apply(dst, [a, b], |[a, b]| a & b);
+    }
+
+    /// `dst = (a ^ ca) & (b ^ cb)`, the AND of two possibly complemented signals.
+    #[inline]
+    pub fn and_compl(&mut self, dst: usize, a: usize, ca: bool, b: usize, cb: bool) {
+        let (ma, mb) = (if ca { u64::MAX } else { 0 }, if cb { u64::MAX } else { 0 });
+        self.apply(dst, [a, b], |[a, b]| (a ^ ma) & (b ^ mb));
+    }
+
+    /// `dst = a | b`.
+    #[inline]
+    pub fn or(&mut self, dst: usize, a: usize, b: usize) {
+        self.apply(dst, [a, b], |[a, b]| a | b);
+    }
+
+    /// `dst = a ^ b`.
+    #[inline]
+    pub fn xor(&mut self, dst: usize, a: usize, b: usize) {
+        self.apply(dst, [a, b], |[a, b]| a ^ b);
+    }
+
+    /// `dst = if c { t } else { e }`.
+    #[inline]
+    pub fn mux(&mut self, dst: usize, c: usize, t: usize, e: usize) {
+        self.apply(dst, [c, t, e], |[c, t, e]| (c & t) | (!c & e));
+    }
+
+    /// The values of signal `s` under all patterns.
+    #[inline]
+    pub fn signal_bitvec(&self, s: usize) -> BitVec {
+        BitVec::from_words(self.signal(s), self.num_patterns())
+    }
+
+    /// The values of all signals under `pattern`, with bit `i` holding signal `i`.
+    pub fn pattern(&self, pattern: usize) -> BitVec {
+        assert!(pattern < self.num_patterns());
+        let (k, j) = (pattern / BitVec::WORD_SIZE, pattern % BitVec::WORD_SIZE);
+        self.data
+            .iter()
+            .skip(k)
+            .step_by(self.num_words)
+            .map(|w| (w >> j) & 1 == 1)
+            .collect()
+    }
+
+    /// Hash of the simulation values of signal `s`.
+    #[inline]
+    pub fn signature(&self, s: usize) -> u64 {
+        RandomState::with_seeds(0, 0, 0, 0).hash_one(self.signal(s))
+    }
+
+    /// Hash of signal `s` up to complementation, and whether `s` was complemented to
+    /// compute it. A signal and its negation get the same hash, so candidate
+    /// equivalences of either polarity land in the same class.
+    #[inline]
+    pub fn signature_normalized(&self, s: usize) -> (u64, bool) {
+        let sig = self.signal(s);
+        let compl = sig.first().is_some_and(|w| w & 1 == 1);
+        if !compl {
+            return (self.signature(s), false);
+        }
+        let neg: Vec<u64> = sig.iter().map(|w| !w).collect();
+        (RandomState::with_seeds(0, 0, 0, 0).hash_one(neg), true)
+    }
+
+    /// Returns true if signals `a` and `b` agree under every pattern.
+    #[inline]
+    pub fn equal(&self, a: usize, b: usize) -> bool {
+        self.signal(a) == self.signal(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_sim_kernels() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut sim = SimVec::new(0, 3);
+        let a = sim.push_random(&mut rng);
+        let b = sim.push_random(&mut rng);
+        let c = sim.push_random(&mut rng);
+        let r: Vec<usize> = (0..7).map(|_| sim.push_zero()).collect();
+        sim.not(r[0], a);
+        sim.and(r[1], a, b);
+        sim.or(r[2], a, b);
+        sim.xor(r[3], a, b);
+        sim.mux(r[4], c, a, b);
+        sim.and_compl(r[5], a, true, b, false);
+        sim.fill(r[6], true);
+        assert_eq!(sim.num_signals(), 10);
+        assert_eq!(sim.num_patterns(), 192);
+        for p in 0..sim.num_patterns() {
+            let (x, y, z) = (sim.get(a, p), sim.get(b, p), sim.get(c, p));
+            let pat = sim.pattern(p);
+            assert_eq!(pat.len(), 10);
+            assert_eq!(pat.get(r[0]), !x);
+            assert_eq!(pat.get(r[1]), x & y);
+            assert_eq!(pat.get(r[2]), x | y);
+            assert_eq!(pat.get(r[3]), x ^ y);
+            assert_eq!(pat.get(r[4]), if z { x } else { y });
+            assert_eq!(pat.get(r[5]), !x & y);
+            assert!(pat.get(r[6]));
+        }
+        sim.and(a, a, b);
+        assert!(sim.equal(a, r[1]));
+        assert_eq!(sim.signal_bitvec(r[1]), sim.signal_bitvec(a));
+        assert_eq!(sim.signal_bitvec(r[1]).as_words(), sim.signal(r[1]));
+    }
+
+    #[test]
+    fn test_sim_signature() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut sim = SimVec::new(0, 4);
+        let a = sim.push_random(&mut rng);
+        let b = sim.push_zero();
+        let c = sim.push_zero();
+        sim.not(b, a);
+        sim.not(c, b);
+        assert_eq!(sim.signature(a), sim.signature(c));
+        assert_ne!(sim.signature(a), sim.signature(b));
+        let (ha, pa) = sim.signature_normalized(a);
+        let (hb, pb) = sim.signature_normalized(b);
+        assert_eq!(ha, hb);
+        assert_ne!(pa, pb);
+        sim.set(c, 100, !sim.get(c, 100));
+        assert_ne!(sim.signature_normalized(c).0, ha);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sim_pattern_out_of_range() {
+        let sim = SimVec::new(3, 2);
+        sim.pattern(130);
+    }
+
+    #[test]
+    fn test_sim_zero_words() {
+        let mut sim = SimVec::new(2, 0);
+        assert_eq!(sim.push_zero(), 2);
+        assert_eq!(sim.push_zero(), 3);
+        assert_eq!(sim.num_signals(), 4);
+        assert_eq!(sim.num_patterns(), 0);
+    }
+}
@@ -0,0 +1,196 @@
+use super::{BitVec, Iter, resolve_range};
+use std::{
+    fmt::{self, Debug},
+    ops::RangeBounds,
+};
+
+/// A borrowed, non-allocating view of a contiguous range of bits in a [`BitVec`].
+#[derive(Clone, Copy)]
+pub struct BitSlice<'a> {
+    bv: &'a BitVec,
+    offset: usize,
+    len: usize,
+}
+
+impl BitVec {
+    /// Borrows the bits in `range`, using the same bounds semantics as [`BitVec::slice`].
+    #[inline]
+    pub fn view<R: RangeBounds<usize>>(&self, range: R) -> BitSlice<'_> {
+        let (l, h) = resolve_range(range, self.len());
+        assert!(l <= h && h <= self.len());
+        BitSlice {
+            bv: self,
+            offset: l,
+            len: h - l,
+        }
+    }
+
+    #[inline]
+    pub fn as_bitslice(&self) -> BitSlice<'_> {
+        self.view(..)
+    }
+}
+
+impl<'a> BitSlice<'a> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn get(&self, index: usize) -> bool {
+        debug_assert!(index < self.len);
+        self.bv.get(self.offset + index)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<'a> {
+        Iter {
+            bv: self.bv,
+            start: self.offset,
+            end: self.offset + self.len,
+        }
+    }
+
+    /// Borrows a sub-range of this view.
+    #[inline]
+    pub fn view<R: RangeBounds<usize>>(&self, range: R) -> BitSlice<'a> {
+        let (l, h) = resolve_range(range, self.len);
+        assert!(l <= h && h <= self.len);
+        BitSlice {
+            bv: self.bv,
+            offset: self.offset + l,
+            len: h - l,
+        }
+    }
+
+    #[inline]
+    fn word_len(&self) -> usize {
+        self.len.div_ceil(BitVec::WORD_SIZE)
+    }
+
+    /// Returns the `i`-th 64-bit chunk of the view, with bits past `len()` cleared.
+    #[inline]
+    fn word(&self, i: usize) -> u64 {
+        let start = i * BitVec::WORD_SIZE;
+        let w = self.bv.word_at(self.offset + start);
+        let rest = self.len - start;
+        if rest < BitVec::WORD_SIZE {
+            w & ((1 << rest) - 1)
+        } else {
+            w
+        }
+    }
+
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        (0..self.word_len())
+            .map(|i| self.word(i).count_ones() as usize)
+            .sum()
+    }
+
+    #[inline]
+    pub fn to_bitvec(&self) -> BitVec {
+        self.bv.slice(self.offset..self.offset + self.len)
+    }
+}
+
+impl<'a> IntoIterator for BitSlice<'a> {
+    type Item = bool;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for BitSlice<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (0..self.word_len()).all(|i| self.word(i) == other.word(i))
+    }
+}
+
+impl Eq for BitSlice<'_> {}
+
+impl PartialEq<BitVec> for BitSlice<'_> {
+    #[inline]
+    fn eq(&self, other: &BitVec) -> bool {
+        *self == other.as_bitslice()
+    }
+}
+
+impl PartialEq<BitSlice<'_>> for BitVec {
+    #[inline]
+    fn eq(&self, other: &BitSlice<'_>) -> bool {
+        self.as_bitslice() == *other
+    }
+}
+
+impl From<BitSlice<'_>> for BitVec {
+    #[inline]
+    fn from(s: BitSlice<'_>) -> Self {
+        s.to_bitvec()
+    }
+}
+
+impl<'a> From<&'a BitVec> for BitSlice<'a> {
+    #[inline]
+    fn from(bv: &'a BitVec) -> Self {
+        bv.as_bitslice()
+    }
+}
+
+impl Debug for BitSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "[]");
+        }
+        let s: String = self
+            .iter()
+            .rev()
+            .map(|b| if b { '1' } else { '0' })
+            .collect();
+        write!(f, "{s}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitslice() {
+        let bv = BitVec::from_iter((0..200).map(|i| i % 3 == 0 || i % 7 == 0));
+        for (l, h) in [
+            (0, 0),
+            (0, 1),
+            (1, 5),
+            (0, 64),
+            (3, 67),
+            (64, 128),
+            (65, 200),
+        ] {
+            let view = bv.view(l..h);
+            let owned = bv.slice(l..h);
+            assert_eq!(view.len(), h - l);
+            assert_eq!(view.to_bitvec(), owned);
+            assert_eq!(view, owned);
+            assert_eq!(owned, view);
+            assert!(view.iter().eq(owned.iter()));
+            assert!((0..view.len()).all(|i| view.get(i) == bv.get(l + i)));
+            assert_eq!(view.count_ones(), owned.iter().filter(|&b| b).count());
+            assert_eq!(format!("{view:?}"), format!("{owned:?}"));
+        }
+        let v = bv.view(10..150);
+        assert_eq!(v.view(5..70), bv.view(15..80));
+        assert_ne!(bv.view(0..10), bv.view(1..11));
+        assert_ne!(bv.view(0..10), bv.view(0..11));
+        assert_eq!(BitVec::from(bv.as_bitslice()), bv);
+    }
+}
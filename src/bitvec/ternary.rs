@@ -0,0 +1,355 @@
+use super::BitVec;
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not},
+};
+
+/// A three-valued bit-vector over `{0, 1, X}`, stored as a value plane and an unknown
+/// plane. A bit is X when its unknown bit is set, in which case its value bit is zero.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct TernaryBitVec {
+    val: BitVec,
+    x: BitVec,
+}
+
+impl TernaryBitVec {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a ternary bit-vector with all `len` bits unknown.
+    #[inline]
+    pub fn new_x(len: usize) -> Self {
+        Self {
+            val: BitVec::zero(len),
+            x: BitVec::from_elem(len, true),
+        }
+    }
+
+    /// Creates a ternary bit-vector from a value plane and an unknown plane.
+    #[inline]
+    pub fn from_planes(val: BitVec, x: BitVec) -> Self {
+        assert!(val.len() == x.len());
+        let mut res = Self { val, x };
+        for (v, x) in res.val.words_mut().iter_mut().zip(res.x.words()) {
+            *v &= !x;
+        }
+        res
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.val.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.val.is_empty()
+    }
+
+    /// The value plane; unknown bits read as zero.
+    #[inline]
+    pub fn val(&self) -> &BitVec {
+        &self.val
+    }
+
+    /// The unknown plane; a set bit marks an X.
+    #[inline]
+    pub fn x(&self) -> &BitVec {
+        &self.x
+    }
+
+    /// Returns the bit at `index`, or `None` if it is X.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (!self.x.get(index)).then(|| self.val.get(index))
+    }
+
+    #[inline]
+    pub fn set(&mut self, index: usize, val: Option<bool>) {
+        self.x.set(index, val.is_none());
+        self.val.set(index, val.unwrap_or(false));
+    }
+
+    #[inline]
+    pub fn push(&mut self, val: Option<bool>) {
+        self.x.push(val.is_none());
+        self.val.push(val.unwrap_or(false));
+    }
+
+    /// Returns true if no bit is X.
+    #[inline]
+    pub fn is_defined(&self) -> bool {
+        self.x.words().iter().all(|&w| w == 0)
+    }
+
+    /// Returns true if every bit defined in `other` has the same value in `self`,
+    /// i.e. `self` is at least as defined as `other` and agrees with it.
+    #[inline]
+    pub fn refines(&self, other: &TernaryBitVec) -> bool {
+        assert!(self.len() == other.len());
+        self.val
+            .words()
+            .iter()
+            .zip(self.x.words())
+            .zip(other.val.words().iter().zip(other.x.words()))
+            .all(|((sv, sx), (ov, ox))| !ox & ((sv ^ ov) | sx) == 0)
+    }
+
+    /// Converts to a [`BitVec`] if every bit is defined.
+    #[inline]
+    pub fn to_bitvec(&self) -> Option<BitVec> {
+        self.is_defined().then(|| self.val.clone())
+    }
+
+    /// Applies `f` word by word to `(val, x)` pairs of `self` and `rhs`.
+    #[inline]
+    fn zip_with(&mut self, rhs: &TernaryBitVec, f: impl Fn(u64, u64, u64, u64) -> (u64, u64)) {
+        assert!(self.len() == rhs.len());
+        let wl = self.val.word_len();
+        for i in 0..wl {
+            let (v, x) = f(
+                self.val.bits[i],
+                self.x.bits[i],
+                rhs.val.bits[i],
+                rhs.x.bits[i],
+            );
+            self.val.bits[i] = v & !x;
+            self.x.bits[i] = x;
+        }
+        self.val.mask_last();
+        self.x.mask_last();
+    }
+
+    #[inline]
+    fn and_in_place(&mut self, rhs: &TernaryBitVec) {
+        self.zip_with(rhs, |av, ax, bv, bx| {
+            let one = av & bv;
+            let zero = (!av & !ax) | (!bv & !bx);
+            (one, !(one | zero))
+        });
+    }
+
+    #[inline]
+    fn or_in_place(&mut self, rhs: &TernaryBitVec) {
+        self.zip_with(rhs, |av, ax, bv, bx| {
+            let one = av | bv;
+            let zero = (!av & !ax) & (!bv & !bx);
+            (one, !(one | zero))
+        });
+    }
+
+    #[inline]
+    fn xor_in_place(&mut self, rhs: &TernaryBitVec) {
+        self.zip_with(rhs, |av, ax, bv, bx| (av ^ bv, ax | bx));
+    }
+}
+
+impl AsRef<Self> for TernaryBitVec {
+    #[inline]
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl From<BitVec> for TernaryBitVec {
+    #[inline]
+    fn from(val: BitVec) -> Self {
+        let x = BitVec::zero(val.len());
+        Self { val, x }
+    }
+}
+
+impl From<&BitVec> for TernaryBitVec {
+    #[inline]
+    fn from(val: &BitVec) -> Self {
+        Self::from(val.clone())
+    }
+}
+
+impl FromIterator<Option<bool>> for TernaryBitVec {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Option<bool>>>(iter: T) -> Self {
+        let mut r = Self::new();
+        for x in iter {
+            r.push(x);
+        }
+        r
+    }
+}
+
+impl Not for TernaryBitVec {
+    type Output = TernaryBitVec;
+
+    #[inline]
+    fn not(mut self) -> TernaryBitVec {
+        for (v, x) in self.val.words_mut().iter_mut().zip(self.x.words()) {
+            *v = !*v & !x;
+        }
+        self.val.mask_last();
+        self
+    }
+}
+
+impl Not for &TernaryBitVec {
+    type Output = TernaryBitVec;
+
+    #[inline]
+    fn not(self) -> TernaryBitVec {
+        !self.clone()
+    }
+}
+
+macro_rules! impl_ternary_bitop {
+    (
+        trait = $trait:ident,
+        method = $method:ident,
+        assign_trait = $assign_trait:ident,
+        assign_method = $assign_method:ident,
+        in_place = $in_place:ident,
+    ) => {
+        impl<R: AsRef<TernaryBitVec>> $trait<R> for TernaryBitVec {
+            type Output = TernaryBitVec;
+
+            #[inline]
+            fn $method(mut self, rhs: R) -> Self::Output {
+                self.$in_place(rhs.as_ref());
+                self
+            }
+        }
+
+        impl<R: AsRef<TernaryBitVec>> $trait<R> for &TernaryBitVec {
+            type Output = TernaryBitVec;
+
+            #[inline]
+            fn $method(self, rhs: R) -> Self::Output {
+                let mut res = self.clone();
+                res.$in_place(rhs.as_ref());
+                res
+            }
+        }
+
+        impl $assign_trait<&TernaryBitVec> for TernaryBitVec {
+            #[inline]
+            fn $assign_method(&mut self, rhs: &TernaryBitVec) {
+                self.$in_place(rhs);
+            }
+        }
+    };
+}
+
+impl_ternary_bitop!(
+    trait = BitAnd,
+    method = bitand,
+    assign_trait = BitAndAssign,
+    assign_method = bitand_assign,
+    in_place = and_in_place,
+);
+
+impl_ternary_bitop!(
+    trait = BitOr,
+    method = bitor,
+    assign_trait = BitOrAssign,
+    assign_method = bitor_assign,
+    in_place = or_in_place,
+);
+
+impl_ternary_bitop!(
+    trait = BitXor,
+    method = bitxor,
+    assign_trait = BitXorAssign,
+    assign_method = bitxor_assign,
+    in_place = xor_in_place,
+);
+
+impl Debug for TernaryBitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.pad("[]");
+        }
+        let s: String = (0..self.len())
+            .rev()
+            .map(|i| match self.get(i) {
+                Some(true) => '1',
+                Some(false) => '0',
+                None => 'x',
+            })
+            .collect();
+        f.pad(&s)
+    }
+}
+
+impl Display for TernaryBitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALS: [Option<bool>; 3] = [Some(false), Some(true), None];
+
+    fn kleene_and(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+        match (a, b) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        }
+    }
+
+    fn kleene_or(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+        match (a, b) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_kleene() {
+        let n = 70;
+        let a = TernaryBitVec::from_iter((0..n).map(|i| VALS[i % 3]));
+        let b = TernaryBitVec::from_iter((0..n).map(|i| VALS[(i / 3) % 3]));
+        let and = &a & &b;
+        let or = &a | &b;
+        let xor = &a ^ &b;
+        let not = !&a;
+        for i in 0..n {
+            let (x, y) = (a.get(i), b.get(i));
+            assert_eq!(and.get(i), kleene_and(x, y));
+            assert_eq!(or.get(i), kleene_or(x, y));
+            assert_eq!(xor.get(i), x.zip(y).map(|(x, y)| x ^ y));
+            assert_eq!(not.get(i), x.map(|x| !x));
+        }
+        assert!(!and.val().intersects(and.x()));
+        assert!(!or.val().intersects(or.x()));
+        assert!(!xor.val().intersects(xor.x()));
+        assert!(!not.val().intersects(not.x()));
+    }
+
+    #[test]
+    fn test_defined_refines() {
+        let bv = BitVec::from("1011");
+        let t = TernaryBitVec::from(&bv);
+        assert!(t.is_defined());
+        assert_eq!(t.to_bitvec(), Some(bv.clone()));
+        let mut u = t.clone();
+        u.set(1, None);
+        assert!(!u.is_defined());
+        assert_eq!(u.to_bitvec(), None);
+        assert!(t.refines(&u));
+        assert!(!u.refines(&t));
+        assert!(t.refines(&TernaryBitVec::new_x(4)));
+        let mut w = t.clone();
+        w.set(0, Some(false));
+        assert!(!w.refines(&u));
+        assert_eq!(format!("{u:?}"), "10x1");
+        assert_eq!(format!("{}", TernaryBitVec::new_x(3)), "xxx");
+        let p = TernaryBitVec::from_planes(BitVec::ones(4), BitVec::from("0011"));
+        assert_eq!(format!("{p:?}"), "11xx");
+        assert_eq!(p.val(), &BitVec::from("1100"));
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    ops::{Deref, DerefMut, Index, IndexMut},
+    slice,
+};
+
+/// Number of words stored inline before spilling to the heap.
+const INLINE: usize = 2;
+
+/// Word storage of a [`BitVec`](super::BitVec). Up to [`INLINE`] words live inline,
+/// longer vectors spill to a heap allocation. The inline length is given by the
+/// variant, so `Words` is no larger than a `Vec<u64>`.
+#[derive(Clone, Debug)]
+pub enum Words {
+    Empty,
+    One(u64),
+    Two([u64; INLINE]),
+    Heap(Vec<u64>),
+}
+
+impl Words {
+    #[inline]
+    pub fn new() -> Self {
+        Words::Empty
+    }
+
+    /// Inline storage holding `data`, which must have at most [`INLINE`] words.
+    #[inline]
+    fn inline(data: &[u64]) -> Self {
+        match *data {
+            [] => Words::Empty,
+            [a] => Words::One(a),
+            [a, b] => Words::Two([a, b]),
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    pub fn from_elem(elem: u64, n: usize) -> Self {
+        if n <= INLINE {
+            Self::inline(&[elem; INLINE][..n])
+        } else {
+            Words::Heap(vec![elem; n])
+        }
+    }
+
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        !matches!(self, Words::Heap(_))
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u64] {
+        match self {
+            Words::Empty => &[],
+            Words::One(w) => slice::from_ref(w),
+            Words::Two(data) => data,
+            Words::Heap(v) => v,
+        }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u64] {
+        match self {
+            Words::Empty => &mut [],
+            Words::One(w) => slice::from_mut(w),
+            Words::Two(data) => data,
+            Words::Heap(v) => v,
+        }
+    }
+
+    #[inline]
+    fn spill(&mut self, additional: usize) -> &mut Vec<u64> {
+        if self.is_inline() {
+            let mut v = Vec::with_capacity(self.len() + additional);
+            v.extend_from_slice(self.as_slice());
+            *self = Words::Heap(v);
+        }
+        match self {
+            Words::Heap(v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, w: u64) {
+        match *self {
+            Words::Empty => *self = Words::One(w),
+            Words::One(a) => *self = Words::Two([a, w]),
+            _ => self.spill(INLINE).push(w),
+        }
+    }
+
+    #[inline]
+    pub fn resize(&mut self, n: usize, w: u64) {
+        if n <= INLINE && self.is_inline() {
+            let mut data = [w; INLINE];
+            let l = self.len().min(n);
+            data[..l].copy_from_slice(&self.as_slice()[..l]);
+            *self = Self::inline(&data[..n]);
+        } else {
+            let l = self.len();
+            self.spill(n.saturating_sub(l)).resize(n, w)
+        }
+    }
+}
+
+impl Default for Words {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Words {
+    type Target = [u64];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for Words {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl Index<usize> for Words {
+    type Output = u64;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        #[cfg(not(debug_assertions))]
+        unsafe {
+            self.as_slice().get_unchecked(index)
+        }
+        #[cfg(debug_assertions)]
+        &self.as_slice()[index]
+    }
+}
+
+impl IndexMut<usize> for Words {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        #[cfg(not(debug_assertions))]
+        unsafe {
+            self.as_mut_slice().get_unchecked_mut(index)
+        }
+        #[cfg(debug_assertions)]
+        &mut self.as_mut_slice()[index]
+    }
+}
+
+impl<'a> IntoIterator for &'a Words {
+    type Item = &'a u64;
+    type IntoIter = slice::Iter<'a, u64>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl FromIterator<u64> for Words {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = u64>>(iter: T) -> Self {
+        let iter = iter.into_iter();
+        if iter.size_hint().1.is_some_and(|n| n <= INLINE) {
+            let mut res = Self::new();
+            for w in iter {
+                res.push(w);
+            }
+            res
+        } else {
+            Words::Heap(iter.collect())
+        }
+    }
+}
+
+impl<const N: usize> From<[u64; N]> for Words {
+    #[inline]
+    fn from(data: [u64; N]) -> Self {
+        Self::from_iter(data)
+    }
+}
+
+impl From<Vec<u64>> for Words {
+    #[inline]
+    fn from(data: Vec<u64>) -> Self {
+        if data.len() <= INLINE {
+            Self::from_iter(data)
+        } else {
+            Words::Heap(data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        let mut w = Words::new();
+        for i in 0..5 {
+            w.push(i);
+            assert_eq!(w.as_slice(), (0..=i).collect::<Vec<_>>());
+            assert_eq!(w.is_inline(), i < INLINE as u64);
+        }
+        w.resize(1, 0);
+        assert_eq!(w.as_slice(), [0]);
+        let mut w = Words::from([7]);
+        w.resize(2, 9);
+        assert_eq!(w.as_slice(), [7, 9]);
+        w.resize(1, 0);
+        w.resize(2, 0);
+        assert_eq!(w.as_slice(), [7, 0]);
+        w.resize(4, 1);
+        assert_eq!(w.as_slice(), [7, 0, 1, 1]);
+        assert!(Words::from(vec![1, 2]).is_inline());
+        assert!(!Words::from_elem(0, 3).is_inline());
+        assert_eq!(
+            size_of::<crate::bitvec::BitVec>(),
+            size_of::<Vec<u64>>() + size_of::<usize>()
+        );
+    }
+}
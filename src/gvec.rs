@@ -1,6 +1,8 @@
 use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
     ops::{Deref, DerefMut, Index, IndexMut},
-    ptr, vec,
+    ptr, slice, vec,
 };
 
 use rand::{
@@ -218,3 +220,293 @@ impl<T> DerefMut for Gvec<T> {
         &mut self.data
     }
 }
+
+/// An index type for [`IdxVec`], usually declared with [`define_idx!`](crate::define_idx).
+pub trait GIdx: Copy + From<u32> + Into<u32> {
+    #[inline]
+    fn index(self) -> usize {
+        self.into() as usize
+    }
+}
+
+impl GIdx for u32 {}
+
+/// Declares a `u32` newtype usable as an [`IdxVec`] index.
+///
+/// ```
+/// giputils::define_idx!(pub struct Var);
+/// let v = Var::from(3);
+/// assert_eq!(u32::from(v), 3);
+/// ```
+#[macro_export]
+macro_rules! define_idx {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident $(;)?) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name(u32);
+
+        impl From<u32> for $name {
+            #[inline]
+            fn from(v: u32) -> Self {
+                Self(v)
+            }
+        }
+
+        impl From<$name> for u32 {
+            #[inline]
+            fn from(v: $name) -> u32 {
+                v.0
+            }
+        }
+
+        impl ::std::fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.0)
+            }
+        }
+
+        impl $crate::gvec::GIdx for $name {}
+    };
+}
+
+/// A [`Gvec`] that can only be indexed by `I`.
+pub struct IdxVec<I, T> {
+    data: Gvec<T>,
+    _i: PhantomData<fn(I) -> I>,
+}
+
+impl<I: GIdx, T> IdxVec<I, T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: Gvec::new(),
+            _i: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The index the next pushed element will get.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from(self.len() as u32)
+    }
+
+    /// Appends `v` and returns its index.
+    #[inline]
+    pub fn push(&mut self, v: T) -> I {
+        let i = self.next_idx();
+        self.data.push(v);
+        i
+    }
+
+    #[inline]
+    pub fn get(&self, i: I) -> Option<&T> {
+        self.data.get(i.index())
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, i: I) -> Option<&mut T> {
+        self.data.get_mut(i.index())
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    #[inline]
+    pub fn indices(&self) -> impl Iterator<Item = I> + use<I, T> {
+        (0..self.len() as u32).map(I::from)
+    }
+
+    #[inline]
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (I, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (I::from(i as u32), v))
+    }
+
+    #[inline]
+    pub fn iter_enumerated_mut(&mut self) -> impl Iterator<Item = (I, &mut T)> {
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(|(i, v)| (I::from(i as u32), v))
+    }
+}
+
+impl<I: GIdx, T: Default> IdxVec<I, T> {
+    /// Grows with default values so that `i` is a valid index.
+    #[inline]
+    pub fn reserve(&mut self, i: I) {
+        self.data.reserve(i.index() + 1);
+    }
+}
+
+impl<I, T> Default for IdxVec<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            data: Gvec::new(),
+            _i: PhantomData,
+        }
+    }
+}
+
+impl<I, T: Clone> Clone for IdxVec<I, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            _i: PhantomData,
+        }
+    }
+}
+
+impl<I, T: Debug> Debug for IdxVec<I, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.data.fmt(f)
+    }
+}
+
+impl<I: GIdx, T> Index<I> for IdxVec<I, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        &self.data[index.index()]
+    }
+}
+
+impl<I: GIdx, T> IndexMut<I> for IdxVec<I, T> {
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.data[index.index()]
+    }
+}
+
+impl<'a, I: GIdx, T> IntoIterator for &'a IdxVec<I, T> {
+    type Item = &'a T;
+
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<I, T> IntoIterator for IdxVec<I, T> {
+    type Item = T;
+
+    type IntoIter = vec::IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<I, A> FromIterator<A> for IdxVec<I, A> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        Self {
+            data: Gvec::from_iter(iter),
+            _i: PhantomData,
+        }
+    }
+}
+
+impl<I, T> From<Vec<T>> for IdxVec<I, T> {
+    #[inline]
+    fn from(data: Vec<T>) -> Self {
+        Self {
+            data: Gvec::from(data),
+            _i: PhantomData,
+        }
+    }
+}
+
+impl<I, T: Serialize> Serialize for IdxVec<I, T> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.data.serialize(serializer)
+    }
+}
+
+impl<'de, I, T: Deserialize<'de>> Deserialize<'de> for IdxVec<I, T> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Gvec::<T>::deserialize(deserializer).map(|data| Self {
+            data,
+            _i: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::define_idx!(
+        struct Var;
+    );
+    crate::define_idx!(
+        /// A clause id.
+        struct ClauseId;
+    );
+
+    #[test]
+    fn test_idx_vec() {
+        let mut v: IdxVec<Var, &str> = IdxVec::new();
+        assert!(v.is_empty());
+        let a = v.push("a");
+        let b = v.push("b");
+        assert_eq!((a, b), (Var::from(0), Var::from(1)));
+        assert_eq!(v[b], "b");
+        v[a] = "c";
+        assert_eq!(v.get(Var::from(2)), None);
+        assert_eq!(
+            v.iter_enumerated().collect::<Vec<_>>(),
+            [(Var::from(0), &"c"), (Var::from(1), &"b")]
+        );
+        assert_eq!(v.indices().collect::<Vec<_>>(), [a, b]);
+        assert_eq!(format!("{a:?}"), "Var(0)");
+        let mut c: IdxVec<ClauseId, u32> = IdxVec::new();
+        c.reserve(ClauseId::from(4));
+        assert_eq!(c.len(), 5);
+        for (i, x) in c.iter_enumerated_mut() {
+            *x = u32::from(i) * 2;
+        }
+        assert_eq!(c[ClauseId::from(3)], 6);
+        assert_eq!(c.next_idx(), ClauseId::from(5));
+    }
+}
@@ -141,8 +141,8 @@ mod tests {
         ptr::Grc,
     };
 
-    struct CMP;
-    impl BinaryHeapCmp<u32> for CMP {
+    struct Cmp;
+    impl BinaryHeapCmp<u32> for Cmp {
         fn gte(&self, s: u32, o: u32) -> bool {
             s <= o
         }
@@ -150,7 +150,7 @@ mod tests {
 
     #[test]
     fn test() {
-        let mut a = BinaryHeap::new(Grc::new(CMP));
+        let mut a = BinaryHeap::new(Grc::new(Cmp));
         for x in [3, 2, 4, 5, 1] {
             a.push(x);
         }